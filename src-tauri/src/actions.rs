@@ -0,0 +1,395 @@
+// 各入口（Tauri 命令、托盘、命令行、外部控制接口）共用的操作逻辑
+
+use crate::cli::{CliCommand, Status};
+use crate::config::{AppConfig, ConfigError};
+use crate::monitor::{get_monitors, MonitorInfo};
+use crate::mouse_watcher::{find_monitor_at_position, get_mouse_position};
+use crate::overlay::OverlayConfig;
+use crate::{focus, hotkeys, i18n, pause, theme, tray, AppState};
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+/// 遮罩层使用的配置
+pub fn overlay_config(app: &AppHandle, config: &AppConfig) -> OverlayConfig {
+    let mut overlay = OverlayConfig {
+        opacity: config.opacity,
+        enabled: config.enabled,
+        animation_duration: config.animation_duration,
+        excluded_monitors: config.excluded_monitors.clone(),
+        color: theme::overlay_color(config, theme::current_theme(app, config)),
+        gradual: config.gradual_dim.enabled.then(|| config.gradual_dim.clone()),
+        recent: (config.recent_monitors.count > 1).then(|| config.recent_monitors.clone()),
+    };
+    focus::apply_overlay(app, config, &mut overlay);
+    overlay
+}
+
+/// 按配置更新遮罩层
+pub fn refresh_overlays(app: &AppHandle, config: &AppConfig) {
+    let state = app.state::<AppState>();
+    let overlay_manager = state.overlay_manager.lock().unwrap();
+
+    if let Some(overlay_manager) = overlay_manager.as_ref() {
+        overlay_manager.update_config(overlay_config(app, config));
+
+        // update_config 会刷新所有遮罩，这里按当前显示器重新计算
+        let current_monitor_id = state.current_monitor_id.lock().unwrap().clone();
+        if let Some(monitor_id) = current_monitor_id {
+            overlay_manager.update_overlays(&get_monitors(), &monitor_id);
+        }
+    }
+}
+
+/// 将配置应用到遮罩层和托盘，并通知所有窗口
+pub fn apply_config(app: &AppHandle, config: &AppConfig) {
+    *app.state::<AppState>().config.lock().unwrap() = config.clone();
+
+    theme::update_theme(app, config);
+    refresh_overlays(app, config);
+    tray::refresh_tray(app, config);
+    hotkeys::register_hotkeys(app, &config.hotkeys);
+
+    let _ = app.emit_all("config-changed", config);
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(service) = app.try_state::<crate::dbus::DbusService>() {
+            service.notify_config_changed();
+        }
+    }
+}
+
+/// 合并配置补丁、保存并重新应用到遮罩层和托盘
+///
+/// 手动修改 enabled 时同时取消暂停。
+pub fn patch_and_apply(app: &AppHandle, mut patch: serde_json::Value) -> Result<AppConfig, ConfigError> {
+    if let Some(fields) = patch.as_object_mut() {
+        if fields.contains_key("enabled") && !fields.contains_key("paused_until") {
+            fields.insert("paused_until".to_string(), serde_json::Value::Null);
+        }
+    }
+
+    let previous = load_config(app).paused_until;
+    let config = app.state::<AppState>().config_manager.lock().unwrap().patch(&patch)?;
+    apply_config(app, &config);
+
+    if config.paused_until != previous {
+        pause::notify_pause_changed(app, &config);
+    }
+    Ok(config)
+}
+
+/// 向用户显示操作失败的原因（按当前语言）
+pub fn report_error(app: &AppHandle, error: &ConfigError) {
+    eprintln!("{}", error);
+
+    let language = load_config(app).language;
+    tauri::api::dialog::message(
+        app.get_window("main").as_ref(),
+        i18n::t(&language, "errorTitle"),
+        i18n::error_message(&language, error),
+    );
+}
+
+/// 当前应用的配置（内存中的副本，不读取文件）
+pub fn load_config(app: &AppHandle) -> AppConfig {
+    app.state::<AppState>().config.lock().unwrap().clone()
+}
+
+/// 切换护眼模式，返回切换后的状态
+pub fn toggle(app: &AppHandle) -> Result<bool, ConfigError> {
+    let enabled = load_config(app).enabled;
+    patch_and_apply(app, json!({ "enabled": !enabled })).map(|config| config.enabled)
+}
+
+/// 设置遮罩透明度
+pub fn set_opacity(app: &AppHandle, opacity: f32) -> Result<(), ConfigError> {
+    patch_and_apply(app, json!({ "opacity": opacity.clamp(0.0, 1.0) })).map(|_| ())
+}
+
+/// 应用预设（名称不区分大小写）
+pub fn apply_preset(app: &AppHandle, name: &str) -> Result<(), ConfigError> {
+    let preset = load_config(app)
+        .find_preset(name)
+        .cloned()
+        .ok_or_else(|| ConfigError::UnknownPreset { name: name.to_string() })?;
+
+    patch_and_apply(app, preset.to_patch()).map(|_| ())
+}
+
+/// 按步长调整透明度，`direction` 为 1.0（增加）或 -1.0（减少）
+pub fn adjust_opacity(app: &AppHandle, direction: f32) -> Result<(), ConfigError> {
+    let config = load_config(app);
+    let opacity = config.opacity + direction * config.opacity_step;
+    // 避免浮点误差累积（如 0.70000005）
+    set_opacity(app, (opacity * 100.0).round() / 100.0)
+}
+
+/// 切换到下一个预设
+pub fn cycle_preset(app: &AppHandle) -> Result<(), ConfigError> {
+    match load_config(app).next_preset() {
+        Some(preset) => patch_and_apply(app, preset.to_patch()).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// 鼠标移动到另一个显示器时调用，锁定期间忽略
+pub fn focus_monitor(app: &AppHandle, monitor_id: String) {
+    let state = app.state::<AppState>();
+    if state.locked_monitor_id.lock().unwrap().is_some() {
+        return;
+    }
+    show_monitor(app, monitor_id);
+}
+
+/// 将指定显示器设为当前显示器并更新遮罩
+fn show_monitor(app: &AppHandle, monitor_id: String) {
+    let state = app.state::<AppState>();
+    *state.current_monitor_id.lock().unwrap() = Some(monitor_id.clone());
+
+    if let Some(manager) = state.overlay_manager.lock().unwrap().as_ref() {
+        manager.update_overlays(&get_monitors(), &monitor_id);
+    }
+
+    notify_monitor_changed(app, &monitor_id);
+    tray::refresh_tray(app, &load_config(app));
+}
+
+/// 锁定指定显示器（None 解除锁定），返回锁定的显示器
+pub fn set_focus_lock(app: &AppHandle, monitor_id: Option<String>) -> Option<String> {
+    let state = app.state::<AppState>();
+    *state.locked_monitor_id.lock().unwrap() = monitor_id.clone();
+
+    match &monitor_id {
+        Some(id) => show_monitor(app, id.clone()),
+        None => {
+            // 解除锁定后回到鼠标所在的显示器
+            let pointer_monitor = get_mouse_position()
+                .and_then(|pos| find_monitor_at_position(&get_monitors(), pos));
+            if let Some(id) = pointer_monitor {
+                show_monitor(app, id);
+            }
+        }
+    }
+
+    let config = load_config(app);
+    tray::refresh_tray(app, &config);
+
+    let _ = app.emit_all("focus-lock-changed", &monitor_id);
+    monitor_id
+}
+
+/// 切换显示器是否显示遮罩
+///
+/// 排除列表按显示器名称保存，显示器序号变化后依然有效。
+pub fn toggle_monitor_excluded(app: &AppHandle, monitor_id: &str) -> Result<(), ConfigError> {
+    let Some(monitor) = get_monitors().into_iter().find(|m| m.id == monitor_id) else {
+        return Ok(());
+    };
+
+    let mut excluded = load_config(app).excluded_monitors;
+    match excluded.iter().position(|name| *name == monitor.name) {
+        Some(index) => {
+            excluded.remove(index);
+        }
+        None => excluded.push(monitor.name),
+    }
+    patch_and_apply(app, json!({ "excluded_monitors": excluded })).map(|_| ())
+}
+
+/// 显示器接入、移除或布局变化后重建遮罩和托盘菜单
+pub fn monitors_changed(app: &AppHandle, monitors: Vec<MonitorInfo>) {
+    let state = app.state::<AppState>();
+
+    // 遮罩窗口的位置和大小按旧布局创建，全部关闭后重新创建
+    if let Some(manager) = state.overlay_manager.lock().unwrap().as_ref() {
+        manager.cleanup();
+    }
+
+    // 锁定的显示器已移除时解除锁定
+    let locked = state.locked_monitor_id.lock().unwrap().clone();
+    if locked.is_some_and(|id| !monitors.iter().any(|m| m.id == id)) {
+        set_focus_lock(app, None);
+    }
+
+    let current_monitor_id = state.current_monitor_id.lock().unwrap().clone();
+    if let (Some(manager), Some(monitor_id)) = (
+        state.overlay_manager.lock().unwrap().as_ref(),
+        current_monitor_id,
+    ) {
+        manager.update_overlays(&monitors, &monitor_id);
+    }
+
+    tray::refresh_tray(app, &load_config(app));
+    let _ = app.emit_all("monitors-changed", &monitors);
+}
+
+/// 锁定当前显示器，已锁定时解除锁定
+pub fn toggle_focus_lock(app: &AppHandle) -> Option<String> {
+    let state = app.state::<AppState>();
+    let locked = state.locked_monitor_id.lock().unwrap().is_some();
+
+    if locked {
+        set_focus_lock(app, None)
+    } else {
+        let current = state.current_monitor_id.lock().unwrap().clone();
+        set_focus_lock(app, current)
+    }
+}
+
+/// 执行从命令行转发来的命令
+pub fn run_cli_command(app: &AppHandle, command: CliCommand) -> Result<(), ConfigError> {
+    match command {
+        CliCommand::Toggle => toggle(app).map(|_| ()),
+        CliCommand::SetOpacity(opacity) => set_opacity(app, opacity),
+        CliCommand::Preset(name) => apply_preset(app, &name),
+        CliCommand::Lock(monitor_id) => {
+            // 与设置界面一样，只能锁定存在的显示器
+            if let Some(id) = &monitor_id {
+                if !get_monitors().iter().any(|monitor| &monitor.id == id) {
+                    return Err(ConfigError::UnknownMonitor { monitor: id.clone() });
+                }
+            }
+            let monitor_id = monitor_id
+                .or_else(|| app.state::<AppState>().current_monitor_id.lock().unwrap().clone());
+            set_focus_lock(app, monitor_id);
+            Ok(())
+        }
+        CliCommand::Unlock => {
+            set_focus_lock(app, None);
+            Ok(())
+        }
+        // status 由启动的进程通过控制接口向正在运行的实例查询，不会转发到这里
+        CliCommand::Status { .. } => Ok(()),
+    }
+}
+
+/// 通知所有监听方：当前显示器已变化
+pub fn notify_monitor_changed(app: &AppHandle, monitor_id: &str) {
+    let _ = app.emit_all("monitor-changed", monitor_id);
+
+    #[cfg(unix)]
+    {
+        if let Some(subscribers) = app.try_state::<crate::ipc::Subscribers>() {
+            subscribers.publish("monitor-changed", json!({ "monitor_id": monitor_id }));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(service) = app.try_state::<crate::dbus::DbusService>() {
+            service.notify_monitor_changed(monitor_id);
+        }
+    }
+}
+
+/// 外部控制接口（Unix socket、D-Bus）可调用的操作
+pub trait Controller: Send + Sync + 'static {
+    fn get_config(&self) -> AppConfig;
+    fn set_opacity(&self, opacity: f32) -> Result<(), ConfigError>;
+    fn set_enabled(&self, enabled: bool) -> Result<(), ConfigError>;
+    fn toggle(&self) -> Result<bool, ConfigError>;
+    fn apply_preset(&self, name: &str) -> Result<(), ConfigError>;
+    fn current_monitor(&self) -> Option<String>;
+    fn monitors(&self) -> Vec<MonitorInfo>;
+    fn status(&self) -> Status;
+}
+
+impl Controller for AppHandle {
+    fn get_config(&self) -> AppConfig {
+        load_config(self)
+    }
+
+    fn set_opacity(&self, opacity: f32) -> Result<(), ConfigError> {
+        set_opacity(self, opacity)
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        patch_and_apply(self, json!({ "enabled": enabled })).map(|_| ())
+    }
+
+    fn toggle(&self) -> Result<bool, ConfigError> {
+        toggle(self)
+    }
+
+    fn apply_preset(&self, name: &str) -> Result<(), ConfigError> {
+        apply_preset(self, name)
+    }
+
+    fn current_monitor(&self) -> Option<String> {
+        self.state::<AppState>().current_monitor_id.lock().unwrap().clone()
+    }
+
+    fn monitors(&self) -> Vec<MonitorInfo> {
+        get_monitors()
+    }
+
+    fn status(&self) -> Status {
+        let locked_monitor = self.state::<AppState>().locked_monitor_id.lock().unwrap().clone();
+        let focus = focus::focus_status(self);
+        Status {
+            locked_monitor,
+            focus_phase: focus.phase,
+            focus_remaining_secs: focus.remaining_secs,
+            ..Status::from_config(&load_config(self), self.current_monitor())
+        }
+    }
+}
+
+/// 测试用的控制器，只修改内存中的配置
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub struct MockController {
+        pub config: Mutex<AppConfig>,
+    }
+
+    impl Controller for MockController {
+        fn get_config(&self) -> AppConfig {
+            self.config.lock().unwrap().clone()
+        }
+
+        fn set_opacity(&self, opacity: f32) -> Result<(), ConfigError> {
+            self.config.lock().unwrap().opacity = opacity.clamp(0.0, 1.0);
+            Ok(())
+        }
+
+        fn set_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+            self.config.lock().unwrap().enabled = enabled;
+            Ok(())
+        }
+
+        fn toggle(&self) -> Result<bool, ConfigError> {
+            let mut config = self.config.lock().unwrap();
+            config.enabled = !config.enabled;
+            Ok(config.enabled)
+        }
+
+        fn apply_preset(&self, name: &str) -> Result<(), ConfigError> {
+            let mut config = self.config.lock().unwrap();
+            let opacity = config
+                .find_preset(name)
+                .map(|preset| preset.opacity)
+                .ok_or_else(|| ConfigError::UnknownPreset {
+                    name: name.to_string(),
+                })?;
+            config.opacity = opacity;
+            Ok(())
+        }
+
+        fn current_monitor(&self) -> Option<String> {
+            Some("monitor_0".to_string())
+        }
+
+        fn monitors(&self) -> Vec<MonitorInfo> {
+            Vec::new()
+        }
+
+        fn status(&self) -> Status {
+            Status::from_config(&self.get_config(), self.current_monitor())
+        }
+    }
+}
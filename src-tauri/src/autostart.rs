@@ -0,0 +1,570 @@
+use crate::config::{AppConfig, ConfigError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 自启动方式（仅 Linux 有效，其他平台始终使用系统自带的方式）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutostartBackend {
+    /// XDG autostart（~/.config/autostart/*.desktop）
+    #[default]
+    Xdg,
+    /// systemd 用户服务（~/.config/systemd/user/monofocus.service）
+    Systemd,
+}
+
+/// 自启动状态
+#[derive(Debug, Clone, Serialize)]
+pub struct AutostartStatus {
+    pub backend: AutostartBackend,
+    /// 自启动项（文件、注册表项或服务单元）已写入
+    pub installed: bool,
+    /// 自启动项已生效
+    pub enabled: bool,
+}
+
+/// 自启动时传给程序的启动选项
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutostartOptions {
+    pub backend: AutostartBackend,
+    /// 启动时不显示主窗口
+    pub hidden: bool,
+    /// 启动延迟（秒）
+    pub delay: u64,
+}
+
+impl AutostartOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            backend: config.autostart_backend,
+            hidden: config.start_hidden,
+            delay: config.startup_delay,
+        }
+    }
+
+    /// 追加到可执行文件之后的命令行参数
+    pub fn launch_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.hidden {
+            args.push("--hidden".to_string());
+        }
+        if self.delay > 0 {
+            args.push("--startup-delay".to_string());
+            args.push(self.delay.to_string());
+        }
+        args
+    }
+}
+
+/// 设置开机自启动
+pub fn set_auto_start(enable: bool, options: &AutostartOptions) -> Result<(), ConfigError> {
+    #[cfg(target_os = "windows")]
+    set_auto_start_windows(enable, options)?;
+
+    #[cfg(target_os = "macos")]
+    set_auto_start_macos(enable, options)?;
+
+    #[cfg(target_os = "linux")]
+    set_auto_start_linux(enable, options)?;
+
+    Ok(())
+}
+
+/// 重写已启用的自启动项，使其指向当前可执行文件和启动选项
+///
+/// 与系统中现有的内容相同时不做任何修改（不写文件，也不调用 systemctl）。
+pub fn refresh_auto_start(options: &AutostartOptions) -> Result<(), ConfigError> {
+    #[cfg(target_os = "windows")]
+    refresh_auto_start_windows(options)?;
+
+    #[cfg(target_os = "macos")]
+    write_if_changed(&plist_path()?, &plist_content(options)?)?;
+
+    #[cfg(target_os = "linux")]
+    match options.backend {
+        AutostartBackend::Xdg => {
+            write_if_changed(&desktop_file_path()?, &desktop_entry(options)?)?;
+        }
+        AutostartBackend::Systemd => {
+            if write_if_changed(&systemd_unit_path()?, &systemd_unit(options)?)? {
+                systemctl(&["daemon-reload"])?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 内容不同时才写入文件，返回是否写入
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+fn write_if_changed(path: &Path, content: &str) -> Result<bool, ConfigError> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(true)
+}
+
+/// 检查系统中实际的自启动项是否生效
+pub fn is_auto_start_installed(backend: AutostartBackend) -> bool {
+    get_auto_start_status(backend).enabled
+}
+
+/// 查询自启动状态
+pub fn get_auto_start_status(backend: AutostartBackend) -> AutostartStatus {
+    #[cfg(target_os = "windows")]
+    {
+        let installed = is_auto_start_installed_windows();
+        return AutostartStatus { backend, installed, enabled: installed };
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let installed = is_auto_start_installed_macos();
+        return AutostartStatus { backend, installed, enabled: installed };
+    }
+
+    #[cfg(target_os = "linux")]
+    return match backend {
+        AutostartBackend::Xdg => get_xdg_status(),
+        AutostartBackend::Systemd => get_systemd_status(),
+    };
+}
+
+#[cfg(target_os = "windows")]
+const RUN_KEY: &str = "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+
+/// 注册表 Run 项中的命令行
+#[cfg(target_os = "windows")]
+fn windows_command_line(options: &AutostartOptions) -> Result<String, ConfigError> {
+    let exe_path = std::env::current_exe()?;
+    let mut command_line = format!("\"{}\"", exe_path.to_string_lossy());
+    for arg in options.launch_args() {
+        command_line.push(' ');
+        command_line.push_str(&arg);
+    }
+    Ok(command_line)
+}
+
+#[cfg(target_os = "windows")]
+fn set_auto_start_windows(enable: bool, options: &AutostartOptions) -> Result<(), ConfigError> {
+    use std::process::Command;
+
+    if enable {
+        let command_line = windows_command_line(options)?;
+
+        Command::new("reg")
+            .args(&[
+                "add",
+                RUN_KEY,
+                "/v",
+                "MonoFocus",
+                "/t",
+                "REG_SZ",
+                "/d",
+                &command_line,
+                "/f",
+            ])
+            .output()?;
+    } else {
+        Command::new("reg")
+            .args(&["delete", RUN_KEY, "/v", "MonoFocus", "/f"])
+            .output()
+            .ok(); // 忽略删除不存在的错误
+    }
+
+    Ok(())
+}
+
+/// 注册表中的命令行不同时才重写
+#[cfg(target_os = "windows")]
+fn refresh_auto_start_windows(options: &AutostartOptions) -> Result<(), ConfigError> {
+    use std::process::Command;
+
+    let command_line = windows_command_line(options)?;
+    let output = Command::new("reg")
+        .args(&["query", RUN_KEY, "/v", "MonoFocus"])
+        .output()?;
+    // 输出形如 "    MonoFocus    REG_SZ    "C:\...\MonoFocus.exe" --hidden"
+    let unchanged = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once("REG_SZ"))
+        .any(|(_, value)| value.trim() == command_line);
+    if !unchanged {
+        set_auto_start_windows(true, options)?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn is_auto_start_installed_windows() -> bool {
+    use std::process::Command;
+
+    Command::new("reg")
+        .args(&["query", RUN_KEY, "/v", "MonoFocus"])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn plist_path() -> Result<PathBuf, ConfigError> {
+    let home = std::env::var("HOME")?;
+    Ok(PathBuf::from(home)
+        .join("Library")
+        .join("LaunchAgents")
+        .join("com.monofocus.app.plist"))
+}
+
+/// LaunchAgent 的 plist 内容
+#[cfg(target_os = "macos")]
+fn plist_content(options: &AutostartOptions) -> Result<String, ConfigError> {
+    let exe_path = std::env::current_exe()?;
+    let arguments: String = std::iter::once(exe_path.to_string_lossy().to_string())
+        .chain(options.launch_args())
+        .map(|arg| format!("        <string>{}</string>\n", escape_xml(&arg)))
+        .collect();
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.monofocus.app</string>
+    <key>ProgramArguments</key>
+    <array>
+{}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>"#,
+        arguments
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn set_auto_start_macos(enable: bool, options: &AutostartOptions) -> Result<(), ConfigError> {
+    let plist_path = plist_path()?;
+
+    if enable {
+        let plist_content = plist_content(options)?;
+        fs::create_dir_all(plist_path.parent().unwrap())?;
+        fs::write(plist_path, plist_content)?;
+    } else {
+        fs::remove_file(plist_path).ok();
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn is_auto_start_installed_macos() -> bool {
+    plist_path().map(|path| path.exists()).unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// XDG 配置目录（遵循 XDG_CONFIG_HOME）
+#[cfg(target_os = "linux")]
+pub(crate) fn xdg_config_home() -> Result<PathBuf, ConfigError> {
+    if let Some(config_home) = std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(config_home));
+    }
+    let home = std::env::var("HOME")?;
+    Ok(PathBuf::from(home).join(".config"))
+}
+
+/// XDG 自启动目录
+#[cfg(target_os = "linux")]
+fn autostart_dir() -> Result<PathBuf, ConfigError> {
+    Ok(xdg_config_home()?.join("autostart"))
+}
+
+/// systemd 用户服务单元文件路径
+#[cfg(target_os = "linux")]
+fn systemd_unit_path() -> Result<PathBuf, ConfigError> {
+    Ok(xdg_config_home()?
+        .join("systemd")
+        .join("user")
+        .join(SYSTEMD_UNIT))
+}
+
+#[cfg(target_os = "linux")]
+const SYSTEMD_UNIT: &str = "monofocus.service";
+
+#[cfg(target_os = "linux")]
+fn set_auto_start_linux(enable: bool, options: &AutostartOptions) -> Result<(), ConfigError> {
+    // 切换方式时清理另一种方式的自启动项，避免重复启动
+    match (enable, options.backend) {
+        (true, AutostartBackend::Xdg) => {
+            set_systemd_autostart(false, options)?;
+            set_xdg_autostart(true, options)
+        }
+        (true, AutostartBackend::Systemd) => {
+            set_xdg_autostart(false, options)?;
+            set_systemd_autostart(true, options)
+        }
+        (false, _) => {
+            set_xdg_autostart(false, options)?;
+            set_systemd_autostart(false, options)
+        }
+    }
+}
+
+/// XDG 自启动项的路径
+#[cfg(target_os = "linux")]
+fn desktop_file_path() -> Result<PathBuf, ConfigError> {
+    Ok(autostart_dir()?.join("monofocus.desktop"))
+}
+
+/// XDG 自启动项的内容
+#[cfg(target_os = "linux")]
+fn desktop_entry(options: &AutostartOptions) -> Result<String, ConfigError> {
+    let exe_path = std::env::current_exe()?;
+    let exec = std::iter::once(exe_path.to_string_lossy().to_string())
+        .chain(options.launch_args())
+        .map(|arg| quote_exec_arg(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(format!(
+        r#"[Desktop Entry]
+Type=Application
+Name=MonoFocus
+Exec={}
+Hidden=false
+NoDisplay=false
+X-GNOME-Autostart-enabled=true"#,
+        exec
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn set_xdg_autostart(enable: bool, options: &AutostartOptions) -> Result<(), ConfigError> {
+    let desktop_file = desktop_file_path()?;
+
+    if enable {
+        let desktop_content = desktop_entry(options)?;
+        fs::create_dir_all(desktop_file.parent().unwrap())?;
+        fs::write(desktop_file, desktop_content)?;
+    } else {
+        fs::remove_file(desktop_file).ok();
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn get_xdg_status() -> AutostartStatus {
+    let content = desktop_file_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok());
+
+    let installed = content.is_some();
+    // 用户可能通过桌面环境的设置界面禁用了该项
+    let enabled = content
+        .map(|content| {
+            !content.lines().any(|line| {
+                let line = line.trim();
+                line == "Hidden=true" || line == "X-GNOME-Autostart-enabled=false"
+            })
+        })
+        .unwrap_or(false);
+
+    AutostartStatus {
+        backend: AutostartBackend::Xdg,
+        installed,
+        enabled,
+    }
+}
+
+/// systemd 用户服务单元的内容
+#[cfg(target_os = "linux")]
+fn systemd_unit(options: &AutostartOptions) -> Result<String, ConfigError> {
+    let exe_path = std::env::current_exe()?;
+    let exec = std::iter::once(exe_path.to_string_lossy().to_string())
+        .chain(options.launch_args())
+        .map(|arg| quote_systemd_arg(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(format!(
+        r#"[Unit]
+Description=MonoFocus
+PartOf=graphical-session.target
+After=graphical-session.target
+
+[Service]
+ExecStart={}
+Restart=on-failure
+RestartSec=5
+
+[Install]
+WantedBy=graphical-session.target
+"#,
+        exec
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn set_systemd_autostart(enable: bool, options: &AutostartOptions) -> Result<(), ConfigError> {
+    let unit_path = systemd_unit_path()?;
+
+    if enable {
+        let unit_content = systemd_unit(options)?;
+        fs::create_dir_all(unit_path.parent().unwrap())?;
+        fs::write(&unit_path, unit_content)?;
+        systemctl(&["daemon-reload"])?;
+        systemctl(&["enable", SYSTEMD_UNIT])?;
+    } else if unit_path.exists() {
+        systemctl(&["disable", SYSTEMD_UNIT]).ok(); // 忽略未启用的错误
+        fs::remove_file(&unit_path)?;
+        systemctl(&["daemon-reload"]).ok();
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn get_systemd_status() -> AutostartStatus {
+    let installed = systemd_unit_path().map(|path| path.exists()).unwrap_or(false);
+    let enabled = installed && systemctl(&["is-enabled", "--quiet", SYSTEMD_UNIT]).is_ok();
+
+    AutostartStatus {
+        backend: AutostartBackend::Systemd,
+        installed,
+        enabled,
+    }
+}
+
+/// 执行 systemctl --user 命令
+#[cfg(target_os = "linux")]
+fn systemctl(args: &[&str]) -> Result<(), ConfigError> {
+    use std::process::Command;
+
+    let output = Command::new("systemctl").arg("--user").args(args).output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(ConfigError::Io {
+            message: format!(
+                "systemctl --user {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        })
+    }
+}
+
+/// 转义 systemd ExecStart 中的单个参数
+///
+/// `%` 和 `$` 会被 systemd 展开，需要写作 `%%`、`$$`；含空白或引号的参数加双引号。
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn quote_systemd_arg(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%").replace('$', "$$");
+    if !escaped.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\') {
+        return escaped;
+    }
+
+    let mut quoted = String::from("\"");
+    for c in escaped.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// 按 Desktop Entry 规范转义 Exec 中的单个参数
+///
+/// 含保留字符的参数需要加双引号，引号内的 `"`、`` ` ``、`$`、`\` 需要反斜杠转义；
+/// 之后整个值作为 string 类型还要再转义一次反斜杠，`%` 写作 `%%`。
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn quote_exec_arg(arg: &str) -> String {
+    const RESERVED: &[char] = &[
+        ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(',
+        ')', '`',
+    ];
+
+    let arg = arg.replace('%', "%%");
+    if !arg.contains(RESERVED) {
+        return arg;
+    }
+
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+
+    quoted.replace('\\', "\\\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_args() {
+        let options = AutostartOptions {
+            hidden: true,
+            delay: 10,
+            ..Default::default()
+        };
+        assert_eq!(options.launch_args(), vec!["--hidden", "--startup-delay", "10"]);
+        assert!(AutostartOptions::default().launch_args().is_empty());
+    }
+
+    #[test]
+    fn test_quote_exec_arg() {
+        assert_eq!(quote_exec_arg("/usr/bin/monofocus"), "/usr/bin/monofocus");
+        assert_eq!(
+            quote_exec_arg("/opt/Mono Focus/monofocus"),
+            "\"/opt/Mono Focus/monofocus\""
+        );
+        assert_eq!(quote_exec_arg("/opt/$app"), "\"/opt/\\\\$app\"");
+        assert_eq!(quote_exec_arg("100%"), "100%%");
+    }
+
+    #[test]
+    fn test_quote_systemd_arg() {
+        assert_eq!(quote_systemd_arg("/usr/bin/monofocus"), "/usr/bin/monofocus");
+        assert_eq!(
+            quote_systemd_arg("/opt/Mono Focus/monofocus"),
+            "\"/opt/Mono Focus/monofocus\""
+        );
+        assert_eq!(quote_systemd_arg("/opt/$app%"), "/opt/$$app%%");
+    }
+
+    #[test]
+    fn test_write_if_changed() {
+        let root = std::env::temp_dir().join(format!("monofocus-autostart-{}", std::process::id()));
+        let path = root.join("autostart").join("monofocus.desktop");
+
+        assert!(write_if_changed(&path, "Exec=monofocus").unwrap());
+        assert!(!write_if_changed(&path, "Exec=monofocus").unwrap());
+        assert!(write_if_changed(&path, "Exec=monofocus --hidden").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Exec=monofocus --hidden");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_backend_serialization() {
+        assert_eq!(serde_json::to_string(&AutostartBackend::Systemd).unwrap(), "\"systemd\"");
+        assert_eq!(AutostartBackend::default(), AutostartBackend::Xdg);
+    }
+}
@@ -0,0 +1,326 @@
+// 休息提醒：按使用时间定时让所有显示器（包括当前显示器）显示遮罩和倒计时
+//
+// 只累计有输入的时间：短暂不操作（阅读）仍然计时，空闲超过 ACTIVE_GRACE_SECS 停止计时，
+// 离开超过 idle_reset_minutes 视为已经休息过，重新计时。暂停护眼模式和专注时段期间不计时。
+
+use crate::actions;
+use crate::focus;
+use crate::config::{AppConfig, BreakConfig};
+use crate::i18n::t;
+use crate::idle;
+use crate::monitor::get_monitors;
+use crate::tray;
+use crate::AppState;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 计时间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 空闲超过这个秒数时停止计时
+const ACTIVE_GRACE_SECS: u64 = 60;
+
+/// 计时产生的事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakEvent {
+    Started { remaining_secs: u64 },
+    Countdown { remaining_secs: u64 },
+    Ended,
+}
+
+/// 休息状态（break-changed 事件的内容）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BreakStatus {
+    pub enabled: bool,
+    pub on_break: bool,
+    /// 休息剩余秒数
+    pub remaining_secs: Option<u64>,
+    /// 距离下次休息的使用秒数
+    pub next_break_secs: Option<u64>,
+}
+
+/// 休息计时（不涉及界面）
+#[derive(Debug, Default)]
+pub struct BreakScheduler {
+    /// 距离下次休息的使用秒数，None 表示从完整的间隔开始
+    until_break: Option<u64>,
+    /// 休息剩余秒数，None 表示不在休息
+    break_remaining: Option<u64>,
+}
+
+impl BreakScheduler {
+    /// 经过 `elapsed` 秒，`idle_secs` 为当前的空闲秒数
+    pub fn tick(&mut self, config: &BreakConfig, elapsed: u64, idle_secs: u64) -> Option<BreakEvent> {
+        if !config.enabled {
+            self.until_break = None;
+            return self.break_remaining.take().map(|_| BreakEvent::Ended);
+        }
+
+        if let Some(remaining) = self.break_remaining {
+            let remaining = remaining.saturating_sub(elapsed);
+            if remaining == 0 {
+                self.break_remaining = None;
+                self.until_break = None;
+                return Some(BreakEvent::Ended);
+            }
+            self.break_remaining = Some(remaining);
+            return Some(BreakEvent::Countdown { remaining_secs: remaining });
+        }
+
+        if idle_secs >= config.idle_reset_minutes * 60 {
+            self.until_break = None;
+            return None;
+        }
+
+        // 间隔改短后不再等待原来的剩余时间
+        let mut until = self.next_break_secs(config);
+        if idle_secs < ACTIVE_GRACE_SECS {
+            until = until.saturating_sub(elapsed);
+        }
+        if until == 0 {
+            return Some(self.start(config));
+        }
+        self.until_break = Some(until);
+        None
+    }
+
+    /// 立即开始休息
+    pub fn start(&mut self, config: &BreakConfig) -> BreakEvent {
+        self.until_break = None;
+        self.break_remaining = Some(config.duration_secs);
+        BreakEvent::Started { remaining_secs: config.duration_secs }
+    }
+
+    /// 结束当前休息，snooze_minutes 分钟后再次提醒（不在休息时无效）
+    pub fn snooze(&mut self, config: &BreakConfig) -> Option<BreakEvent> {
+        self.break_remaining.take()?;
+        self.until_break = Some(config.snooze_minutes * 60);
+        Some(BreakEvent::Ended)
+    }
+
+    /// 跳过这次休息，重新开始完整的间隔
+    pub fn skip(&mut self) -> Option<BreakEvent> {
+        self.until_break = None;
+        self.break_remaining.take().map(|_| BreakEvent::Ended)
+    }
+
+    pub fn on_break(&self) -> bool {
+        self.break_remaining.is_some()
+    }
+
+    fn next_break_secs(&self, config: &BreakConfig) -> u64 {
+        let interval = config.interval_minutes * 60;
+        self.until_break.map_or(interval, |until| until.min(interval))
+    }
+
+    pub fn status(&self, config: &BreakConfig) -> BreakStatus {
+        BreakStatus {
+            enabled: config.enabled,
+            on_break: self.on_break(),
+            remaining_secs: self.break_remaining,
+            next_break_secs: (config.enabled && !self.on_break()).then(|| self.next_break_secs(config)),
+        }
+    }
+}
+
+/// 休息计时状态
+#[derive(Default)]
+pub struct BreakState {
+    scheduler: Mutex<BreakScheduler>,
+}
+
+/// 当前的休息状态
+pub fn break_status(app: &AppHandle, config: &AppConfig) -> BreakStatus {
+    match app.try_state::<BreakState>() {
+        Some(state) => state.scheduler.lock().unwrap().status(&config.breaks),
+        None => BreakScheduler::default().status(&config.breaks),
+    }
+}
+
+/// 立即开始休息
+pub fn start_break(app: &AppHandle) -> BreakStatus {
+    update_scheduler(app, |scheduler, config| Some(scheduler.start(config)))
+}
+
+/// 推迟休息
+pub fn snooze_break(app: &AppHandle) -> BreakStatus {
+    update_scheduler(app, |scheduler, config| scheduler.snooze(config))
+}
+
+/// 跳过这次休息
+pub fn skip_break(app: &AppHandle) -> BreakStatus {
+    update_scheduler(app, |scheduler, _| scheduler.skip())
+}
+
+/// 修改计时并按产生的事件更新遮罩，返回修改后的状态
+fn update_scheduler<F>(app: &AppHandle, update: F) -> BreakStatus
+where
+    F: FnOnce(&mut BreakScheduler, &BreakConfig) -> Option<BreakEvent>,
+{
+    let config = actions::load_config(app);
+    let Some(state) = app.try_state::<BreakState>() else {
+        return BreakScheduler::default().status(&config.breaks);
+    };
+
+    let (event, status) = {
+        let mut scheduler = state.scheduler.lock().unwrap();
+        let event = update(&mut scheduler, &config.breaks);
+        (event, scheduler.status(&config.breaks))
+    };
+    handle_event(app, &config, event, &status);
+    status
+}
+
+/// 显示、更新或隐藏休息遮罩，并通知前端和托盘
+fn handle_event(app: &AppHandle, config: &AppConfig, event: Option<BreakEvent>, status: &BreakStatus) {
+    let Some(event) = event else {
+        return;
+    };
+
+    let state = app.state::<AppState>();
+    let overlay_manager = state.overlay_manager.lock().unwrap();
+    if let Some(overlay_manager) = overlay_manager.as_ref() {
+        match event {
+            BreakEvent::Started { remaining_secs } => overlay_manager.show_break(
+                &get_monitors(),
+                config.breaks.opacity,
+                remaining_secs,
+                &t(&config.language, "break.title"),
+                &t(&config.language, "break.hint"),
+            ),
+            BreakEvent::Countdown { remaining_secs } => overlay_manager.update_break(remaining_secs),
+            BreakEvent::Ended => overlay_manager.hide_break(),
+        }
+    }
+    drop(overlay_manager);
+
+    if event == BreakEvent::Ended {
+        // 恢复休息前的遮罩
+        actions::refresh_overlays(app, config);
+    }
+    if !matches!(event, BreakEvent::Countdown { .. }) {
+        tray::refresh_tray(app, config);
+    }
+    let _ = app.emit_all("break-changed", status);
+}
+
+/// 启动休息计时线程
+pub fn start_break_timer(app: AppHandle) {
+    thread::spawn(move || {
+        let mut next_minutes = None;
+
+        loop {
+            thread::sleep(TICK_INTERVAL);
+
+            let config = actions::load_config(&app);
+            let Some(state) = app.try_state::<BreakState>() else {
+                continue;
+            };
+
+            let (event, status) = {
+                let mut scheduler = state.scheduler.lock().unwrap();
+                // 暂停和专注时段期间不计时（正在进行的休息照常结束）
+                let suspended = config.paused_until.is_some() || focus::is_active(&app);
+                if suspended && !scheduler.on_break() {
+                    continue;
+                }
+                let idle = idle::idle_seconds(&app);
+                let event = scheduler.tick(&config.breaks, TICK_INTERVAL.as_secs(), idle);
+                (event, scheduler.status(&config.breaks))
+            };
+            handle_event(&app, &config, event, &status);
+
+            // 托盘显示距离下次休息的分钟数，变化时才刷新
+            let minutes = status.next_break_secs.map(crate::pause::remaining_minutes);
+            if minutes != next_minutes {
+                next_minutes = minutes;
+                tray::refresh_tray(&app, &config);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BreakConfig {
+        BreakConfig {
+            enabled: true,
+            interval_minutes: 1,
+            duration_secs: 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_break_cycle() {
+        let config = config();
+        let mut scheduler = BreakScheduler::default();
+
+        assert_eq!(scheduler.tick(&config, 59, 0), None);
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(1));
+        assert_eq!(
+            scheduler.tick(&config, 1, 0),
+            Some(BreakEvent::Started { remaining_secs: 10 })
+        );
+        assert_eq!(
+            scheduler.tick(&config, 4, 0),
+            Some(BreakEvent::Countdown { remaining_secs: 6 })
+        );
+        assert_eq!(scheduler.status(&config).remaining_secs, Some(6));
+        assert_eq!(scheduler.tick(&config, 6, 0), Some(BreakEvent::Ended));
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(60));
+    }
+
+    #[test]
+    fn test_idle() {
+        let config = config();
+        let mut scheduler = BreakScheduler::default();
+
+        scheduler.tick(&config, 30, 0);
+        // 短暂离开停止计时
+        assert_eq!(scheduler.tick(&config, 30, ACTIVE_GRACE_SECS), None);
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(30));
+
+        // 离开足够久视为已经休息
+        scheduler.tick(&config, 1, config.idle_reset_minutes * 60);
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(60));
+    }
+
+    #[test]
+    fn test_snooze_and_skip() {
+        let config = config();
+        let mut scheduler = BreakScheduler::default();
+
+        scheduler.start(&config);
+        assert_eq!(scheduler.snooze(&config), Some(BreakEvent::Ended));
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(60));
+        assert_eq!(scheduler.snooze(&config), None);
+
+        let config = BreakConfig {
+            interval_minutes: 20,
+            ..config
+        };
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(5 * 60));
+
+        scheduler.start(&config);
+        assert_eq!(scheduler.skip(), Some(BreakEvent::Ended));
+        assert_eq!(scheduler.status(&config).next_break_secs, Some(20 * 60));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut config = config();
+        let mut scheduler = BreakScheduler::default();
+        scheduler.start(&config);
+
+        config.enabled = false;
+        assert_eq!(scheduler.tick(&config, 1, 0), Some(BreakEvent::Ended));
+        assert_eq!(scheduler.tick(&config, 120, 0), None);
+        assert_eq!(scheduler.status(&config).next_break_secs, None);
+    }
+}
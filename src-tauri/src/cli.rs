@@ -0,0 +1,289 @@
+use crate::config::{AppConfig, MAX_STARTUP_DELAY};
+use crate::focus::FocusPhase;
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// 命令行用法
+pub const USAGE: &str = "Usage: monofocus [OPTIONS] [COMMAND]
+
+Commands:
+  toggle               Toggle eye care mode
+  set-opacity <0-1>    Set overlay opacity, e.g. 0.4
+  preset <name>        Apply a preset, e.g. Reading
+  lock [monitor]       Keep a monitor undimmed (default: the current one)
+  unlock               Follow the mouse again
+  status [--json]      Print the current status
+
+Options:
+  --config-dir <path>      Use a custom config directory
+  --hidden                 Start without showing the main window
+  --startup-delay <secs>   Wait before starting";
+
+/// 命令行命令（转发给已运行的实例执行）
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Toggle,
+    SetOpacity(f32),
+    Preset(String),
+    Lock(Option<String>),
+    Unlock,
+    Status { json: bool },
+}
+
+/// 命令行参数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliOptions {
+    /// 配置目录（`--config-dir <path>` 或 `--config-dir=<path>`）
+    pub config_dir: Option<PathBuf>,
+    /// 启动时不显示主窗口（`--hidden`）
+    pub hidden: bool,
+    /// 启动延迟秒数（`--startup-delay <secs>`）
+    pub startup_delay: u64,
+    /// 要执行的命令
+    pub command: Option<CliCommand>,
+}
+
+/// 命令行参数错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// 解析命令行参数（第一个参数为程序路径，忽略）
+pub fn parse_args<I>(args: I) -> Result<CliOptions, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = CliOptions::default();
+    let mut args = args.into_iter().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--config-dir" {
+            let dir = args.next().unwrap_or_default();
+            options.config_dir = Some(parse_config_dir(&dir)?);
+        } else if let Some(dir) = arg.strip_prefix("--config-dir=") {
+            options.config_dir = Some(parse_config_dir(dir)?);
+        } else if arg == "--hidden" {
+            options.hidden = true;
+        } else if arg == "--startup-delay" {
+            let value = args
+                .next()
+                .ok_or_else(|| CliError("--startup-delay requires a number of seconds".to_string()))?;
+            options.startup_delay = match value.parse() {
+                Ok(delay) if delay <= MAX_STARTUP_DELAY => delay,
+                _ => {
+                    return Err(CliError(format!(
+                        "invalid startup delay: {} (0-{} seconds)",
+                        value, MAX_STARTUP_DELAY
+                    )))
+                }
+            };
+        } else if arg == "--json" {
+            if let Some(CliCommand::Status { json }) = options.command.as_mut() {
+                *json = true;
+            }
+        } else if arg.starts_with("--") {
+            // 忽略未知选项（例如桌面环境附加的参数）
+        } else if let Some(CliCommand::Lock(monitor_id @ None)) = options.command.as_mut() {
+            *monitor_id = Some(arg);
+        } else if options.command.is_none() {
+            options.command = Some(parse_command(&arg, &mut args)?);
+        } else {
+            return Err(CliError(format!("unexpected argument: {}", arg)));
+        }
+    }
+
+    Ok(options)
+}
+
+/// 配置目录不能为空，否则会悄悄使用默认目录
+fn parse_config_dir(dir: &str) -> Result<PathBuf, CliError> {
+    if dir.is_empty() {
+        return Err(CliError("--config-dir requires a path".to_string()));
+    }
+    Ok(PathBuf::from(dir))
+}
+
+/// 解析命令及其参数
+fn parse_command<I>(name: &str, args: &mut I) -> Result<CliCommand, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    match name {
+        "toggle" => Ok(CliCommand::Toggle),
+        "set-opacity" => {
+            let value = args
+                .next()
+                .ok_or_else(|| CliError("set-opacity requires a value between 0 and 1".to_string()))?;
+            // 超出范围时取最近的有效值，与其他控制接口一致
+            match value.parse::<f32>() {
+                Ok(opacity) if opacity.is_finite() => Ok(CliCommand::SetOpacity(opacity.clamp(0.0, 1.0))),
+                _ => Err(CliError(format!("invalid opacity: {}", value))),
+            }
+        }
+        "preset" => args
+            .next()
+            .map(CliCommand::Preset)
+            .ok_or_else(|| CliError("preset requires a name".to_string())),
+        "lock" => Ok(CliCommand::Lock(None)),
+        "unlock" => Ok(CliCommand::Unlock),
+        "status" => Ok(CliCommand::Status { json: false }),
+        _ => Err(CliError(format!("unknown command: {}", name))),
+    }
+}
+
+/// status 命令输出的状态（由正在运行的实例提供）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+    pub enabled: bool,
+    pub opacity: f32,
+    pub animation_duration: u64,
+    pub language: String,
+    pub active_monitor: Option<String>,
+    pub locked_monitor: Option<String>,
+    /// 暂停截止时间（Unix 时间戳，秒）
+    pub paused_until: Option<u64>,
+    pub focus_phase: Option<FocusPhase>,
+    /// 专注时段当前阶段的剩余秒数
+    pub focus_remaining_secs: Option<u64>,
+}
+
+impl Status {
+    /// 只包含配置中的状态（没有正在运行的实例时使用）
+    pub fn from_config(config: &AppConfig, active_monitor: Option<String>) -> Self {
+        Self {
+            enabled: config.enabled,
+            opacity: config.opacity,
+            animation_duration: config.animation_duration,
+            language: config.language.clone(),
+            active_monitor,
+            locked_monitor: None,
+            paused_until: config.paused_until,
+            focus_phase: None,
+            focus_remaining_secs: None,
+        }
+    }
+}
+
+/// 格式化 status 命令的输出
+pub fn format_status(status: &Status, as_json: bool) -> String {
+    if as_json {
+        return serde_json::to_string(status).unwrap_or_default();
+    }
+
+    let mut lines = vec![
+        format!("enabled: {}", status.enabled),
+        format!("opacity: {:.0}%", status.opacity * 100.0),
+        format!("active monitor: {}", status.active_monitor.as_deref().unwrap_or("-")),
+    ];
+    if let Some(monitor_id) = &status.locked_monitor {
+        lines.push(format!("locked monitor: {}", monitor_id));
+    }
+    if let Some(until) = status.paused_until.and_then(|until| Local.timestamp_opt(until as i64, 0).single()) {
+        lines.push(format!("paused until: {}", until.format("%Y-%m-%d %H:%M")));
+    }
+    if let (Some(phase), Some(remaining)) = (status.focus_phase, status.focus_remaining_secs) {
+        let phase = match phase {
+            FocusPhase::Focus => "focus",
+            FocusPhase::Break => "break",
+        };
+        lines.push(format!("focus session: {} ({}:{:02} left)", phase, remaining / 60, remaining % 60));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_config_dir() {
+        let options = parse_args(args(&["monofocus", "--config-dir", "/tmp/mf"])).unwrap();
+        assert_eq!(options.config_dir, Some(PathBuf::from("/tmp/mf")));
+
+        let options = parse_args(args(&["monofocus", "--config-dir=/tmp/mf"])).unwrap();
+        assert_eq!(options.config_dir, Some(PathBuf::from("/tmp/mf")));
+
+        assert!(parse_args(args(&["monofocus", "--config-dir"])).is_err());
+        assert!(parse_args(args(&["monofocus", "--config-dir="])).is_err());
+
+        let options = parse_args(args(&["monofocus"])).unwrap();
+        assert_eq!(options, CliOptions::default());
+    }
+
+    #[test]
+    fn test_parse_launch_options() {
+        let options = parse_args(args(&["monofocus", "--hidden", "--startup-delay", "15"])).unwrap();
+        assert!(options.hidden);
+        assert_eq!(options.startup_delay, 15);
+
+        assert!(parse_args(args(&["monofocus", "--startup-delay", "soon"])).is_err());
+        assert!(parse_args(args(&["monofocus", "--startup-delay", "601"])).is_err());
+        assert!(parse_args(args(&["monofocus", "--startup-delay"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let options = parse_args(args(&["monofocus", "toggle"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::Toggle));
+
+        let options = parse_args(args(&["monofocus", "set-opacity", "0.4"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::SetOpacity(0.4)));
+
+        let options = parse_args(args(&["monofocus", "set-opacity", "1.5"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::SetOpacity(1.0)));
+
+        let options = parse_args(args(&["monofocus", "preset", "Reading"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::Preset("Reading".to_string())));
+
+        let options = parse_args(args(&["monofocus", "lock", "monitor_1"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::Lock(Some("monitor_1".to_string()))));
+
+        let options = parse_args(args(&["monofocus", "lock"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::Lock(None)));
+
+        let options = parse_args(args(&["monofocus", "status", "--json"])).unwrap();
+        assert_eq!(options.command, Some(CliCommand::Status { json: true }));
+    }
+
+    #[test]
+    fn test_parse_invalid_commands() {
+        assert!(parse_args(args(&["monofocus", "set-opacity", "half"])).is_err());
+        assert!(parse_args(args(&["monofocus", "set-opacity", "NaN"])).is_err());
+        assert!(parse_args(args(&["monofocus", "set-opacity"])).is_err());
+        assert!(parse_args(args(&["monofocus", "preset"])).is_err());
+        assert!(parse_args(args(&["monofocus", "dance"])).is_err());
+    }
+
+    #[test]
+    fn test_format_status() {
+        let config = AppConfig::default();
+        let mut status = Status::from_config(&config, Some("monitor_1".to_string()));
+        let output = format_status(&status, true);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["enabled"], true);
+        assert_eq!(value["active_monitor"], "monitor_1");
+        assert_eq!(value["locked_monitor"], serde_json::Value::Null);
+
+        let output = format_status(&status, false);
+        assert!(output.contains("opacity: 60%"));
+        assert!(!output.contains("locked monitor"));
+
+        status.locked_monitor = Some("monitor_1".to_string());
+        status.focus_phase = Some(FocusPhase::Focus);
+        status.focus_remaining_secs = Some(12 * 60 + 5);
+        let output = format_status(&status, false);
+        assert!(output.contains("locked monitor: monitor_1"));
+        assert!(output.contains("focus session: focus (12:05 left)"));
+    }
+}
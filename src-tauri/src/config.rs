@@ -1,6 +1,127 @@
+use crate::autostart::{
+    get_auto_start_status, is_auto_start_installed, refresh_auto_start, set_auto_start,
+    AutostartBackend, AutostartOptions, AutostartStatus,
+};
+use crate::hotkeys::validate_hotkeys;
+use crate::policy::{system_config_dirs, LockedSettings, SystemLayers};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 支持的界面语言
+pub const SUPPORTED_LANGUAGES: &[&str] = &["zh", "en", "ja", "fr", "de", "es"];
+
+/// 跟随系统语言（每次启动时按 LC_ALL / LC_MESSAGES / LANG 检测）
+pub const AUTO_LANGUAGE: &str = "auto";
+
+/// 支持的主题
+pub const SUPPORTED_THEMES: &[&str] = &["auto", "light", "dark"];
+
+/// 指定配置目录的环境变量
+pub const CONFIG_DIR_ENV: &str = "MONOFOCUS_CONFIG_DIR";
+
+/// 便携模式标记文件（放在可执行文件旁）
+const PORTABLE_MARKER: &str = "monofocus.portable";
+
+/// 自启动延迟上限（秒）
+pub const MAX_STARTUP_DELAY: u64 = 600;
+
+/// 动画时长上限（毫秒）
+pub const MAX_ANIMATION_DURATION: u64 = 2000;
+
+/// 离开后变暗的等待时长上限（分钟）
+pub const MAX_IDLE_DIM_MINUTES: u64 = 120;
+
+/// 保持不变暗的最近使用显示器数量上限
+pub const MAX_RECENT_MONITORS: u64 = 8;
+
+/// 配置错误，序列化后带有机器可读的 `code` 字段供前端区分处理
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum ConfigError {
+    UnknownLanguage { language: String },
+    DurationOutOfRange { duration: u64, max: u64 },
+    DelayOutOfRange { delay: u64, max: u64 },
+    InvalidTheme { theme: String },
+    OpacityOutOfRange { opacity: f32 },
+    UnknownField { field: String },
+    UnknownPreset { name: String },
+    UnknownMonitor { monitor: String },
+    Locked { field: String },
+    OpacityBelowMinimum { opacity: f32, min: f32 },
+    InvalidHotkey { action: String, accelerator: String },
+    HotkeyConflict { accelerator: String, actions: Vec<String> },
+    OpacityStepOutOfRange { step: f32 },
+    OutOfRange { field: String, value: u64, min: u64, max: u64 },
+    InvalidValue { message: String },
+    InvalidFile { message: String },
+    Io { message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnknownLanguage { language } => {
+                write!(f, "unknown language: {}", language)
+            }
+            ConfigError::DurationOutOfRange { duration, max } => {
+                write!(f, "animation duration {}ms is out of range (0-{}ms)", duration, max)
+            }
+            ConfigError::DelayOutOfRange { delay, max } => {
+                write!(f, "startup delay {}s is out of range (0-{}s)", delay, max)
+            }
+            ConfigError::InvalidTheme { theme } => write!(f, "invalid theme: {}", theme),
+            ConfigError::OpacityOutOfRange { opacity } => {
+                write!(f, "opacity {} is out of range (0.0-1.0)", opacity)
+            }
+            ConfigError::UnknownField { field } => write!(f, "unknown config field: {}", field),
+            ConfigError::Locked { field } => write!(f, "{} is locked by the administrator", field),
+            ConfigError::OpacityBelowMinimum { opacity, min } => {
+                write!(f, "opacity {} is below the administrator minimum {}", opacity, min)
+            }
+            ConfigError::UnknownPreset { name } => write!(f, "unknown preset: {}", name),
+            ConfigError::UnknownMonitor { monitor } => write!(f, "unknown monitor: {}", monitor),
+            ConfigError::InvalidHotkey { action, accelerator } => {
+                write!(f, "invalid hotkey for {}: {}", action, accelerator)
+            }
+            ConfigError::HotkeyConflict { accelerator, actions } => {
+                write!(f, "hotkey {} is used by both {}", accelerator, actions.join(" and "))
+            }
+            ConfigError::OpacityStepOutOfRange { step } => {
+                write!(f, "opacity step {} is out of range (0.01-0.5)", step)
+            }
+            ConfigError::OutOfRange { field, value, min, max } => {
+                write!(f, "{} {} is out of range ({}-{})", field, value, min, max)
+            }
+            ConfigError::InvalidValue { message } => write!(f, "invalid value: {}", message),
+            ConfigError::InvalidFile { message } => write!(f, "invalid config file: {}", message),
+            ConfigError::Io { message } => write!(f, "io error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io { message: e.to_string() }
+    }
+}
+
+impl From<std::env::VarError> for ConfigError {
+    fn from(e: std::env::VarError) -> Self {
+        ConfigError::Io { message: e.to_string() }
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        ConfigError::InvalidFile { message: e.to_string() }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -9,10 +130,244 @@ pub struct AppConfig {
     pub auto_start: bool,
     #[serde(default = "default_theme")]
     pub theme: String,
+    #[serde(default)]
+    pub theme_overlay: bool,      // 遮罩颜色跟随主题（浅色主题使用深灰）
+    #[serde(default = "default_true")]
+    pub theme_tray_icon: bool,    // 深色主题使用浅色的托盘图标
     #[serde(default = "default_animation_duration")]
     pub animation_duration: u64,  // 动画时长（毫秒），0 表示无动画
     #[serde(default = "default_language")]
-    pub language: String,         // "zh" 或 "en"
+    pub language: String,         // "auto"（跟随系统）或 SUPPORTED_LANGUAGES 之一
+    #[serde(default)]
+    pub start_hidden: bool,       // 自启动时不显示主窗口
+    #[serde(default)]
+    pub startup_delay: u64,       // 自启动延迟（秒）
+    #[serde(default)]
+    pub autostart_backend: AutostartBackend, // 自启动方式（仅 Linux）
+    #[serde(default = "default_presets")]
+    pub presets: Vec<Preset>,
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
+    #[serde(default = "default_opacity_step")]
+    pub opacity_step: f32,        // 快捷键每次调整的透明度
+    #[serde(default)]
+    pub paused_until: Option<u64>, // 暂停截止时间（Unix 时间戳，秒），到期后自动恢复
+    #[serde(default)]
+    pub excluded_monitors: Vec<String>, // 不显示遮罩的显示器（按显示器名称）
+    #[serde(default)]
+    pub gradual_dim: GradualDimConfig,
+    #[serde(default)]
+    pub recent_monitors: RecentMonitorsConfig,
+    #[serde(default)]
+    pub idle_dim_minutes: u64,    // 离开超过这个时长后所有显示器都显示遮罩（分钟），0 表示关闭
+    #[serde(default)]
+    pub breaks: BreakConfig,
+    #[serde(default)]
+    pub focus: FocusConfig,
+}
+
+/// 全局快捷键（空字符串表示不绑定）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeyConfig {
+    pub toggle: String,
+    pub opacity_up: String,
+    pub opacity_down: String,
+    pub lock_monitor: String,
+    pub cycle_preset: String,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            toggle: "CmdOrCtrl+Alt+M".to_string(),
+            opacity_up: "CmdOrCtrl+Alt+Up".to_string(),
+            opacity_down: "CmdOrCtrl+Alt+Down".to_string(),
+            lock_monitor: "CmdOrCtrl+Alt+L".to_string(),
+            cycle_preset: "CmdOrCtrl+Alt+P".to_string(),
+        }
+    }
+}
+
+/// 最近使用的显示器：鼠标最近访问过的 count 台显示器（包括当前显示器）不变暗，
+/// rank_opacity 大于 0 时按访问的先后逐台加深
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecentMonitorsConfig {
+    pub count: u64,        // 1 表示只有当前显示器不变暗
+    pub rank_opacity: f32, // 每往前一位增加的透明度，0 表示完全不变暗
+}
+
+impl Default for RecentMonitorsConfig {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            rank_opacity: 0.0,
+        }
+    }
+}
+
+impl RecentMonitorsConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        validate_range("recent_monitors.count", self.count, 1, MAX_RECENT_MONITORS)?;
+        validate_opacity(self.rank_opacity)
+    }
+}
+
+/// 逐渐变暗的曲线（横轴为离开后经过的时间占 ramp_minutes 的比例）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DimCurve {
+    /// 匀速变暗
+    #[default]
+    Linear,
+    /// 开始时变化慢，之后加快
+    EaseIn,
+    /// 开始时变化快，之后放缓
+    EaseOut,
+}
+
+/// 逐渐变暗：非活跃显示器的遮罩从 start_opacity 开始，按离开的时长逐渐加深到 max_opacity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GradualDimConfig {
+    pub enabled: bool,
+    pub start_opacity: f32, // 鼠标刚离开时的透明度
+    pub max_opacity: f32,   // 加深的上限
+    pub ramp_minutes: u64,  // 从 start_opacity 加深到 max_opacity 的时长（分钟）
+    pub curve: DimCurve,
+}
+
+impl Default for GradualDimConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_opacity: 0.3,
+            max_opacity: 0.8,
+            ramp_minutes: 10,
+            curve: DimCurve::Linear,
+        }
+    }
+}
+
+impl GradualDimConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        validate_opacity(self.start_opacity)?;
+        validate_opacity(self.max_opacity)?;
+        if self.start_opacity > self.max_opacity {
+            return Err(ConfigError::InvalidValue {
+                message: "gradual_dim.start_opacity must not exceed gradual_dim.max_opacity".to_string(),
+            });
+        }
+        validate_range("gradual_dim.ramp_minutes", self.ramp_minutes, 1, 120)
+    }
+}
+
+/// 休息提醒：每使用 interval_minutes 分钟，所有显示器（包括当前显示器）显示 duration_secs 秒的遮罩
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BreakConfig {
+    pub enabled: bool,
+    pub interval_minutes: u64,   // 两次休息之间的使用时间（分钟）
+    pub duration_secs: u64,      // 休息时长（秒）
+    pub opacity: f32,            // 休息遮罩的透明度
+    pub snooze_minutes: u64,     // 推迟休息的时长（分钟）
+    pub idle_reset_minutes: u64, // 离开超过这个时长视为已经休息，重新计时（分钟）
+}
+
+impl Default for BreakConfig {
+    fn default() -> Self {
+        // 20-20-20：每 20 分钟看 20 英尺外 20 秒
+        Self {
+            enabled: false,
+            interval_minutes: 20,
+            duration_secs: 20,
+            opacity: 0.9,
+            snooze_minutes: 5,
+            idle_reset_minutes: 5,
+        }
+    }
+}
+
+impl BreakConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        validate_opacity(self.opacity)?;
+        validate_range("breaks.interval_minutes", self.interval_minutes, 1, 240)?;
+        validate_range("breaks.duration_secs", self.duration_secs, 5, 1800)?;
+        validate_range("breaks.snooze_minutes", self.snooze_minutes, 1, 60)?;
+        validate_range("breaks.idle_reset_minutes", self.idle_reset_minutes, 1, 120)
+    }
+}
+
+/// 专注时段（番茄钟）：focus_minutes 分钟的专注之后休息 break_minutes 分钟
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusConfig {
+    pub focus_minutes: u64, // 专注时长（分钟）
+    pub break_minutes: u64, // 专注结束后的休息时长（分钟）
+    pub opacity: f32,       // 专注期间非活跃显示器的遮罩透明度
+    pub blackout: bool,     // 专注期间非活跃显示器完全变黑（忽略 opacity）
+    pub lock_monitor: bool, // 开始时锁定到当前显示器
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            focus_minutes: 25,
+            break_minutes: 5,
+            opacity: 0.9,
+            blackout: false,
+            lock_monitor: false,
+        }
+    }
+}
+
+impl FocusConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        validate_opacity(self.opacity)?;
+        validate_range("focus.focus_minutes", self.focus_minutes, 1, 180)?;
+        validate_range("focus.break_minutes", self.break_minutes, 1, 60)
+    }
+}
+
+/// 预设：一组常用的遮罩设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub opacity: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation_duration: Option<u64>,
+}
+
+impl Preset {
+    fn new(name: &str, opacity: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            opacity,
+            animation_duration: None,
+        }
+    }
+
+    /// 应用预设时使用的配置补丁
+    pub fn to_patch(&self) -> Value {
+        let mut patch = json!({ "opacity": self.opacity });
+        if let Some(duration) = self.animation_duration {
+            patch["animation_duration"] = json!(duration);
+        }
+        patch
+    }
+}
+
+fn default_presets() -> Vec<Preset> {
+    vec![
+        Preset::new("Reading", 0.8),
+        Preset::new("Coding", 0.6),
+        Preset::new("Presentation", 0.3),
+    ]
+}
+
+fn default_opacity_step() -> f32 {
+    0.1
 }
 
 fn default_animation_duration() -> u64 {
@@ -20,7 +375,11 @@ fn default_animation_duration() -> u64 {
 }
 
 fn default_language() -> String {
-    "zh".to_string() // 默认中文
+    AUTO_LANGUAGE.to_string() // 首次运行时跟随系统语言
+}
+
+fn default_true() -> bool {
+    true
 }
 
 fn default_theme() -> String {
@@ -34,28 +393,477 @@ impl Default for AppConfig {
             enabled: true,
             auto_start: false,
             theme: "auto".to_string(),
+            theme_overlay: false,
+            theme_tray_icon: true,
             animation_duration: 300,
-            language: "zh".to_string(),
+            language: default_language(),
+            start_hidden: false,
+            startup_delay: 0,
+            autostart_backend: AutostartBackend::Xdg,
+            presets: default_presets(),
+            hotkeys: HotkeyConfig::default(),
+            opacity_step: default_opacity_step(),
+            paused_until: None,
+            excluded_monitors: Vec::new(),
+            gradual_dim: GradualDimConfig::default(),
+            recent_monitors: RecentMonitorsConfig::default(),
+            idle_dim_minutes: 0,
+            breaks: BreakConfig::default(),
+            focus: FocusConfig::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// 校验配置字段
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        validate_opacity(self.opacity)?;
+        validate_duration(self.animation_duration)?;
+        for preset in &self.presets {
+            validate_opacity(preset.opacity)?;
+            if let Some(duration) = preset.animation_duration {
+                validate_duration(duration)?;
+            }
+        }
+        if self.startup_delay > MAX_STARTUP_DELAY {
+            return Err(ConfigError::DelayOutOfRange {
+                delay: self.startup_delay,
+                max: MAX_STARTUP_DELAY,
+            });
+        }
+        if self.language != AUTO_LANGUAGE && !SUPPORTED_LANGUAGES.contains(&self.language.as_str()) {
+            return Err(ConfigError::UnknownLanguage { language: self.language.clone() });
         }
+        if !SUPPORTED_THEMES.contains(&self.theme.as_str()) {
+            return Err(ConfigError::InvalidTheme { theme: self.theme.clone() });
+        }
+        if !(0.01..=0.5).contains(&self.opacity_step) {
+            return Err(ConfigError::OpacityStepOutOfRange { step: self.opacity_step });
+        }
+        validate_hotkeys(&self.hotkeys)?;
+        self.gradual_dim.validate()?;
+        self.recent_monitors.validate()?;
+        validate_range("idle_dim_minutes", self.idle_dim_minutes, 0, MAX_IDLE_DIM_MINUTES)?;
+        self.breaks.validate()?;
+        self.focus.validate()?;
+        Ok(())
+    }
+
+    /// 当前设置之后的下一个预设（当前设置不属于任何预设时返回第一个）
+    pub fn next_preset(&self) -> Option<&Preset> {
+        let current = self
+            .presets
+            .iter()
+            .position(|preset| (preset.opacity - self.opacity).abs() < 0.001);
+
+        match current {
+            Some(index) => self.presets.get((index + 1) % self.presets.len()),
+            None => self.presets.first(),
+        }
+    }
+
+    /// 按名称查找预设（不区分大小写）
+    pub fn find_preset(&self, name: &str) -> Option<&Preset> {
+        self.presets
+            .iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+}
+
+fn validate_opacity(opacity: f32) -> Result<(), ConfigError> {
+    if (0.0..=1.0).contains(&opacity) {
+        Ok(())
+    } else {
+        Err(ConfigError::OpacityOutOfRange { opacity })
+    }
+}
+
+/// 整数设置的取值范围，`field` 为完整的字段路径（如 "breaks.duration_secs"）
+fn validate_range(field: &str, value: u64, min: u64, max: u64) -> Result<(), ConfigError> {
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ConfigError::OutOfRange {
+            field: field.to_string(),
+            value,
+            min,
+            max,
+        })
     }
 }
 
+fn validate_duration(duration: u64) -> Result<(), ConfigError> {
+    if duration <= MAX_ANIMATION_DURATION {
+        Ok(())
+    } else {
+        Err(ConfigError::DurationOutOfRange {
+            duration,
+            max: MAX_ANIMATION_DURATION,
+        })
+    }
+}
+
+/// 将补丁字段合并到配置对象中，拒绝未知字段
+///
+/// 嵌套对象（如 hotkeys）逐个字段合并，只修改补丁中出现的字段。
+fn merge_fields(value: &mut Value, fields: &Map<String, Value>) -> Result<(), ConfigError> {
+    for (key, field) in fields {
+        match (value.get_mut(key), field) {
+            (Some(slot @ Value::Object(_)), Value::Object(nested)) => {
+                merge_fields(slot, nested).map_err(|e| match e {
+                    ConfigError::UnknownField { field } => ConfigError::UnknownField {
+                        field: format!("{}.{}", key, field),
+                    },
+                    e => e,
+                })?
+            }
+            (Some(slot), _) => *slot = field.clone(),
+            (None, _) => return Err(ConfigError::UnknownField { field: key.clone() }),
+        }
+    }
+    Ok(())
+}
+
+/// 将配置层合并到配置对象中，忽略未知字段（兼容旧版本或手写的文件）
+pub(crate) fn merge_layer(value: &mut Value, fields: &Map<String, Value>) {
+    for (key, field) in fields {
+        match (value.get_mut(key), field) {
+            (Some(slot @ Value::Object(_)), Value::Object(nested)) => merge_layer(slot, nested),
+            (Some(slot), _) => *slot = field.clone(),
+            (None, _) => {}
+        }
+    }
+}
+
+/// 写出 JSON Schema，内容相同时不写（避免每次启动都写入便携设备），返回是否写入
+fn write_schema(path: &Path) -> std::io::Result<bool> {
+    let schema = serde_json::to_string_pretty(&config_schema())?;
+    if fs::read_to_string(path).is_ok_and(|existing| existing == schema) {
+        return Ok(false);
+    }
+    fs::write(path, schema)?;
+    Ok(true)
+}
+
+/// 生成 config.json 的 JSON Schema（供编辑器校验）
+pub fn config_schema() -> serde_json::Value {
+    let languages = [&[AUTO_LANGUAGE], SUPPORTED_LANGUAGES].concat();
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "MonoFocus config",
+        "type": "object",
+        "properties": {
+            "opacity": {
+                "type": "number",
+                "minimum": 0.0,
+                "maximum": 1.0,
+                "description": "Overlay opacity (0.0 - 1.0)"
+            },
+            "enabled": { "type": "boolean" },
+            "auto_start": { "type": "boolean" },
+            "theme": {
+                "type": "string",
+                "enum": SUPPORTED_THEMES,
+                "default": "auto",
+                "description": "Light or dark appearance; \"auto\" follows the desktop setting"
+            },
+            "theme_overlay": {
+                "type": "boolean",
+                "default": false,
+                "description": "Use a softer dark gray overlay with the light theme instead of black"
+            },
+            "theme_tray_icon": {
+                "type": "boolean",
+                "default": true,
+                "description": "Use a light tray icon with the dark theme"
+            },
+            "animation_duration": {
+                "type": "integer",
+                "minimum": 0,
+                "maximum": MAX_ANIMATION_DURATION,
+                "default": 300,
+                "description": "Animation duration in milliseconds, 0 disables animation"
+            },
+            "language": {
+                "type": "string",
+                "enum": languages,
+                "default": AUTO_LANGUAGE,
+                "description": "Interface language; \"auto\" follows the system locale"
+            },
+            "start_hidden": {
+                "type": "boolean",
+                "default": false,
+                "description": "Start without showing the main window when launched at login"
+            },
+            "startup_delay": {
+                "type": "integer",
+                "minimum": 0,
+                "maximum": MAX_STARTUP_DELAY,
+                "default": 0,
+                "description": "Delay in seconds before starting when launched at login"
+            },
+            "autostart_backend": {
+                "type": "string",
+                "enum": ["xdg", "systemd"],
+                "default": "xdg",
+                "description": "Autostart mechanism on Linux: XDG autostart entry or systemd user service"
+            },
+            "presets": {
+                "type": "array",
+                "description": "Named overlay settings that can be applied from the tray or command line",
+                "items": {
+                    "type": "object",
+                    "required": ["name", "opacity"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "opacity": { "type": "number", "minimum": 0.0, "maximum": 1.0 },
+                        "animation_duration": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "maximum": MAX_ANIMATION_DURATION
+                        }
+                    }
+                }
+            },
+            "hotkeys": {
+                "type": "object",
+                "description": "Global shortcuts, e.g. \"CmdOrCtrl+Alt+M\"; an empty string disables the shortcut",
+                "additionalProperties": false,
+                "properties": {
+                    "toggle": { "type": "string", "default": "CmdOrCtrl+Alt+M" },
+                    "opacity_up": { "type": "string", "default": "CmdOrCtrl+Alt+Up" },
+                    "opacity_down": { "type": "string", "default": "CmdOrCtrl+Alt+Down" },
+                    "lock_monitor": { "type": "string", "default": "CmdOrCtrl+Alt+L" },
+                    "cycle_preset": { "type": "string", "default": "CmdOrCtrl+Alt+P" }
+                }
+            },
+            "opacity_step": {
+                "type": "number",
+                "minimum": 0.01,
+                "maximum": 0.5,
+                "default": 0.1,
+                "description": "Opacity change per opacity_up / opacity_down shortcut"
+            },
+            "paused_until": {
+                "type": ["integer", "null"],
+                "minimum": 0,
+                "default": null,
+                "description": "Unix timestamp (seconds) at which a pause ends and eye care mode is re-enabled"
+            },
+            "excluded_monitors": {
+                "type": "array",
+                "items": { "type": "string" },
+                "default": [],
+                "description": "Names of monitors that are never dimmed, e.g. \"HDMI-1\""
+            },
+            "gradual_dim": {
+                "type": "object",
+                "description": "Dim inactive monitors gradually, the longer the pointer has been away from them; replaces opacity when enabled",
+                "additionalProperties": false,
+                "properties": {
+                    "enabled": { "type": "boolean", "default": false },
+                    "start_opacity": {
+                        "type": "number",
+                        "minimum": 0.0,
+                        "maximum": 1.0,
+                        "default": 0.3,
+                        "description": "Opacity right after the pointer leaves a monitor"
+                    },
+                    "max_opacity": {
+                        "type": "number",
+                        "minimum": 0.0,
+                        "maximum": 1.0,
+                        "default": 0.8,
+                        "description": "Deepest opacity, reached after ramp_minutes"
+                    },
+                    "ramp_minutes": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 120,
+                        "default": 10,
+                        "description": "Minutes to go from start_opacity to max_opacity"
+                    },
+                    "curve": {
+                        "type": "string",
+                        "enum": ["linear", "ease_in", "ease_out"],
+                        "default": "linear",
+                        "description": "Shape of the ramp: steady, slow start, or fast start"
+                    }
+                }
+            },
+            "recent_monitors": {
+                "type": "object",
+                "description": "Keep the monitors most recently visited by the pointer undimmed, or lightly dimmed by recency",
+                "additionalProperties": false,
+                "properties": {
+                    "count": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_RECENT_MONITORS,
+                        "default": 1,
+                        "description": "Number of recently visited monitors, including the active one, that are not fully dimmed; 1 dims every other monitor"
+                    },
+                    "rank_opacity": {
+                        "type": "number",
+                        "minimum": 0.0,
+                        "maximum": 1.0,
+                        "default": 0.0,
+                        "description": "Opacity added per step back in visit order, capped at the normal opacity; 0 keeps them undimmed"
+                    }
+                }
+            },
+            "idle_dim_minutes": {
+                "type": "integer",
+                "minimum": 0,
+                "maximum": MAX_IDLE_DIM_MINUTES,
+                "default": 0,
+                "description": "Dim every monitor, including the active one, after this many minutes without input; 0 disables"
+            },
+            "breaks": {
+                "type": "object",
+                "description": "Break reminders: every interval_minutes of use, all monitors are dimmed for duration_secs",
+                "additionalProperties": false,
+                "properties": {
+                    "enabled": { "type": "boolean", "default": false },
+                    "interval_minutes": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 240,
+                        "default": 20,
+                        "description": "Minutes of use between breaks"
+                    },
+                    "duration_secs": {
+                        "type": "integer",
+                        "minimum": 5,
+                        "maximum": 1800,
+                        "default": 20,
+                        "description": "Length of a break in seconds"
+                    },
+                    "opacity": {
+                        "type": "number",
+                        "minimum": 0.0,
+                        "maximum": 1.0,
+                        "default": 0.9,
+                        "description": "Overlay opacity during a break, also used for the break after a focus session"
+                    },
+                    "snooze_minutes": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 60,
+                        "default": 5,
+                        "description": "Minutes a snoozed break is postponed"
+                    },
+                    "idle_reset_minutes": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 120,
+                        "default": 5,
+                        "description": "Being away for this long counts as a break and restarts the timer"
+                    }
+                }
+            },
+            "focus": {
+                "type": "object",
+                "description": "Focus sessions: focus_minutes of deeper dimming followed by a break of break_minutes",
+                "additionalProperties": false,
+                "properties": {
+                    "focus_minutes": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 180,
+                        "default": 25,
+                        "description": "Length of the focus phase in minutes"
+                    },
+                    "break_minutes": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 60,
+                        "default": 5,
+                        "description": "Length of the break that ends a session in minutes"
+                    },
+                    "opacity": {
+                        "type": "number",
+                        "minimum": 0.0,
+                        "maximum": 1.0,
+                        "default": 0.9,
+                        "description": "Overlay opacity of inactive monitors during the focus phase"
+                    },
+                    "blackout": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Black out inactive monitors completely during the focus phase"
+                    },
+                    "lock_monitor": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Lock focus to the current monitor for the focus phase"
+                    }
+                }
+            }
+        }
+    })
+}
+
 pub struct ConfigManager {
     config_path: PathBuf,
+    system_dirs: Vec<PathBuf>,
+    // 最近一次由程序自己写入的内容，文件监听据此忽略自己的保存
+    last_written: Mutex<Option<String>>,
 }
 
 impl ConfigManager {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_dir = Self::get_config_dir()?;
+    /// 创建配置管理器，`config_dir` 为命令行指定的配置目录（优先级最高）
+    pub fn new(config_dir: Option<PathBuf>) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_dir = match config_dir {
+            Some(dir) => dir,
+            None => Self::get_config_dir()?,
+        };
         fs::create_dir_all(&config_dir)?;
         
         let config_path = config_dir.join("config.json");
+
+        // 写出 JSON Schema，方便在编辑器中校验手动修改
+        if let Err(e) = write_schema(&config_dir.join("config.schema.json")) {
+            eprintln!("Failed to write config schema: {}", e);
+        }
         
-        Ok(Self { config_path })
+        Ok(Self {
+            config_path,
+            system_dirs: system_config_dirs(),
+            last_written: Mutex::new(None),
+        })
     }
 
     /// 获取配置文件目录
+    ///
+    /// 优先级：环境变量 MONOFOCUS_CONFIG_DIR > 便携模式 > 平台默认目录
     fn get_config_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            return Ok(PathBuf::from(dir));
+        }
+
+        if let Some(dir) = Self::portable_config_dir() {
+            return Ok(dir);
+        }
+
+        Self::platform_config_dir()
+    }
+
+    /// 便携模式：可执行文件旁存在标记文件时，配置保存在可执行文件旁的 config 目录
+    fn portable_config_dir() -> Option<PathBuf> {
+        let exe_path = std::env::current_exe().ok()?;
+        let exe_dir = exe_path.parent()?;
+
+        if exe_dir.join(PORTABLE_MARKER).exists() {
+            Some(exe_dir.join("config"))
+        } else {
+            None
+        }
+    }
+
+    /// 平台默认配置目录
+    fn platform_config_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
         #[cfg(target_os = "windows")]
         {
             let appdata = std::env::var("APPDATA")?;
@@ -82,170 +890,170 @@ impl ConfigManager {
         }
     }
 
+    /// 配置文件路径
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
     /// 加载配置
     pub fn load(&self) -> AppConfig {
-        if let Ok(content) = fs::read_to_string(&self.config_path) {
-            if let Ok(config) = serde_json::from_str::<AppConfig>(&content) {
-                return config;
-            }
+        if let Ok(config) = self.try_load() {
+            return config;
         }
-        
-        // 如果加载失败，返回默认配置并保存
-        let default_config = AppConfig::default();
-        let _ = self.save(&default_config);
-        default_config
+
+        // 文件不存在或内容无效时使用默认值（含系统默认值），不覆盖用户的文件
+        let layers = self.layers();
+        let mut value = self.base_value(&layers);
+        layers.enforce(&mut value);
+        serde_json::from_value(value).unwrap_or_default()
     }
 
-    /// 保存配置
-    pub fn save(&self, config: &AppConfig) -> Result<(), Box<dyn std::error::Error>> {
-        let content = serde_json::to_string_pretty(config)?;
-        fs::write(&self.config_path, content)?;
-        Ok(())
+    /// 加载并校验配置文件，失败时返回具体错误（用于外部修改的校验）
+    ///
+    /// 合并顺序：内置默认值 < 系统默认值 < 用户配置 < 管理员锁定值
+    pub fn try_load(&self) -> Result<AppConfig, ConfigError> {
+        let content = fs::read_to_string(&self.config_path)?;
+        let user = match serde_json::from_str::<Value>(&content)? {
+            Value::Object(object) => object,
+            _ => {
+                return Err(ConfigError::InvalidFile {
+                    message: "config must be a JSON object".to_string(),
+                })
+            }
+        };
+
+        let layers = self.layers();
+        let mut value = self.base_value(&layers);
+        merge_layer(&mut value, &user);
+        layers.enforce(&mut value);
+
+        let config = serde_json::from_value::<AppConfig>(value)?;
+        config.validate()?;
+        Ok(config)
     }
 
-    /// 更新单个字段
-    pub fn update_opacity(&self, opacity: f32) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = self.load();
-        config.opacity = opacity.clamp(0.0, 1.0);
-        self.save(&config)
+    /// 修改前读取当前配置：文件不存在时使用默认值，文件存在但无效时返回错误
+    ///
+    /// 无效的配置文件只上报，不能与补丁合并后覆盖用户的其他设置。
+    fn load_for_update(&self) -> Result<AppConfig, ConfigError> {
+        if !self.config_path.exists() {
+            return Ok(self.load());
+        }
+        self.try_load().map_err(|e| match e {
+            ConfigError::InvalidFile { .. } | ConfigError::Io { .. } => e,
+            e => ConfigError::InvalidFile { message: e.to_string() },
+        })
     }
-    
-    pub fn update_animation_duration(&self, duration: u64) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = self.load();
-        config.animation_duration = duration;
-        self.save(&config)
+
+    /// 以系统中实际的自启动项为准同步 auto_start，返回同步后的配置
+    ///
+    /// 已启用时更新自启动项，使其指向当前可执行文件和启动选项（程序更新或移动后依然有效），
+    /// 内容未变时不做修改。
+    pub fn reconcile_auto_start(&self) -> Result<AppConfig, ConfigError> {
+        let mut config = self.load_for_update()?;
+        let installed = is_auto_start_installed(config.autostart_backend);
+
+        if installed {
+            refresh_auto_start(&AutostartOptions::from_config(&config))?;
+        }
+
+        if config.auto_start != installed {
+            config.auto_start = installed;
+            self.save(&config)?;
+        }
+
+        Ok(config)
     }
-    
-    pub fn update_language(&self, language: String) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = self.load();
-        config.language = language;
-        self.save(&config)
+
+    /// 当前自启动方式的实际状态
+    pub fn auto_start_status(&self) -> AutostartStatus {
+        get_auto_start_status(self.load().autostart_backend)
     }
 
-    pub fn update_enabled(&self, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = self.load();
-        config.enabled = enabled;
-        self.save(&config)
+    /// 当前的锁定信息
+    pub fn locked_settings(&self) -> LockedSettings {
+        self.layers().locked_settings()
     }
 
-    pub fn update_auto_start(&self, auto_start: bool) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = self.load();
-        config.auto_start = auto_start;
-        self.save(&config)?;
-        
-        // 实际设置开机自启动
-        #[cfg(target_os = "windows")]
-        self.set_auto_start_windows(auto_start)?;
-        
-        #[cfg(target_os = "macos")]
-        self.set_auto_start_macos(auto_start)?;
-        
-        #[cfg(target_os = "linux")]
-        self.set_auto_start_linux(auto_start)?;
-        
-        Ok(())
+    /// 加载系统级配置层
+    fn layers(&self) -> SystemLayers {
+        SystemLayers::load(&self.system_dirs)
     }
 
-    #[cfg(target_os = "windows")]
-    fn set_auto_start_windows(&self, enable: bool) -> Result<(), Box<dyn std::error::Error>> {
-        use std::process::Command;
-        
-        let exe_path = std::env::current_exe()?;
-        let exe_path_str = exe_path.to_string_lossy();
-        
-        if enable {
-            Command::new("reg")
-                .args(&[
-                    "add",
-                    "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run",
-                    "/v",
-                    "MonoFocus",
-                    "/t",
-                    "REG_SZ",
-                    "/d",
-                    &exe_path_str,
-                    "/f",
-                ])
-                .output()?;
-        } else {
-            Command::new("reg")
-                .args(&[
-                    "delete",
-                    "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run",
-                    "/v",
-                    "MonoFocus",
-                    "/f",
-                ])
-                .output()
-                .ok(); // 忽略删除不存在的错误
-        }
-        
+    /// 内置默认值与系统默认值合并后的配置
+    fn base_value(&self, layers: &SystemLayers) -> Value {
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap_or_default();
+        merge_layer(&mut value, &layers.defaults);
+        value
+    }
+
+    /// 保存配置
+    ///
+    /// 只写入与默认值（含系统默认值）不同的字段，系统默认值的后续调整仍能生效。
+    pub fn save(&self, config: &AppConfig) -> Result<(), ConfigError> {
+        config.validate()?;
+
+        let base = self.base_value(&self.layers());
+        let user: Map<String, Value> = match serde_json::to_value(config)? {
+            Value::Object(object) => object
+                .into_iter()
+                .filter(|(key, field)| base.get(key) != Some(field))
+                .collect(),
+            _ => Map::new(),
+        };
+        let content = serde_json::to_string_pretty(&user)?;
+
+        // 先写临时文件再重命名，避免写入中途被读取到不完整的内容
+        let tmp_path = self.config_path.with_extension("json.tmp");
+        fs::write(&tmp_path, &content)?;
+        fs::rename(&tmp_path, &self.config_path)?;
+        *self.last_written.lock().unwrap() = Some(content);
         Ok(())
     }
 
-    #[cfg(target_os = "macos")]
-    fn set_auto_start_macos(&self, enable: bool) -> Result<(), Box<dyn std::error::Error>> {
-        let home = std::env::var("HOME")?;
-        let plist_path = PathBuf::from(home)
-            .join("Library")
-            .join("LaunchAgents")
-            .join("com.monofocus.app.plist");
-        
-        if enable {
-            let exe_path = std::env::current_exe()?;
-            let plist_content = format!(
-                r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
-<plist version="1.0">
-<dict>
-    <key>Label</key>
-    <string>com.monofocus.app</string>
-    <key>ProgramArguments</key>
-    <array>
-        <string>{}</string>
-    </array>
-    <key>RunAtLoad</key>
-    <true/>
-</dict>
-</plist>"#,
-                exe_path.to_string_lossy()
-            );
-            
-            fs::create_dir_all(plist_path.parent().unwrap())?;
-            fs::write(plist_path, plist_content)?;
+    /// 文件内容是否为程序自己最近一次保存的内容（已经应用过，不需要重新加载）
+    ///
+    /// 匹配一次后清除记录，之后外部改回相同的内容仍会被加载。
+    pub fn take_own_write(&self, content: &str) -> bool {
+        let mut last_written = self.last_written.lock().unwrap();
+        if last_written.as_deref() == Some(content) {
+            *last_written = None;
+            true
         } else {
-            fs::remove_file(plist_path).ok();
+            false
         }
-        
-        Ok(())
     }
 
-    #[cfg(target_os = "linux")]
-    fn set_auto_start_linux(&self, enable: bool) -> Result<(), Box<dyn std::error::Error>> {
-        let home = std::env::var("HOME")?;
-        let autostart_dir = PathBuf::from(home).join(".config").join("autostart");
-        let desktop_file = autostart_dir.join("monofocus.desktop");
-        
-        if enable {
-            let exe_path = std::env::current_exe()?;
-            let desktop_content = format!(
-                r#"[Desktop Entry]
-Type=Application
-Name=MonoFocus
-Exec={}
-Hidden=false
-NoDisplay=false
-X-GNOME-Autostart-enabled=true"#,
-                exe_path.to_string_lossy()
-            );
-            
-            fs::create_dir_all(&autostart_dir)?;
-            fs::write(desktop_file, desktop_content)?;
-        } else {
-            fs::remove_file(desktop_file).ok();
+    /// 合并部分字段并保存
+    ///
+    /// 补丁先与当前配置合并并完整校验，全部通过后才写入文件，任何一项失败都不会修改配置。
+    pub fn patch(&self, patch: &serde_json::Value) -> Result<AppConfig, ConfigError> {
+        let fields = patch.as_object().ok_or_else(|| ConfigError::InvalidValue {
+            message: "patch must be a JSON object".to_string(),
+        })?;
+
+        // 被管理员锁定的字段不允许修改
+        self.layers().check_patch(fields)?;
+
+        let current = self.load_for_update()?;
+        let mut value = serde_json::to_value(&current)?;
+        merge_fields(&mut value, fields)?;
+
+        let config: AppConfig = serde_json::from_value(value).map_err(|e| {
+            ConfigError::InvalidValue { message: e.to_string() }
+        })?;
+        config.validate()?;
+
+        // 自启动需要同步修改系统设置（启动选项变化时也要重写），成功后再保存
+        let options = AutostartOptions::from_config(&config);
+        if config.auto_start != current.auto_start
+            || (config.auto_start && options != AutostartOptions::from_config(&current))
+        {
+            set_auto_start(config.auto_start, &options)?;
         }
-        
-        Ok(())
+
+        self.save(&config)?;
+        Ok(config)
     }
 }
 
@@ -268,5 +1076,238 @@ mod tests {
         assert!(json.contains("opacity"));
         assert!(json.contains("enabled"));
     }
+
+    #[test]
+    fn test_validate_config() {
+        assert!(AppConfig::default().validate().is_ok());
+
+        let config = AppConfig {
+            language: "xx".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::UnknownLanguage { language: "xx".to_string() })
+        );
+
+        let config = AppConfig {
+            animation_duration: MAX_ANIMATION_DURATION + 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DurationOutOfRange { .. })
+        ));
+
+        let config = AppConfig {
+            theme: "neon".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidTheme { .. })));
+
+        let mut config = AppConfig::default();
+        config.gradual_dim.start_opacity = 0.9;
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidValue { .. })));
+
+        let mut config = AppConfig::default();
+        config.recent_monitors.count = 0;
+        assert!(matches!(config.validate(), Err(ConfigError::OutOfRange { .. })));
+
+        let config = AppConfig {
+            idle_dim_minutes: MAX_IDLE_DIM_MINUTES + 1,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(ConfigError::OutOfRange { .. })));
+
+        let mut config = AppConfig::default();
+        config.breaks.duration_secs = 1;
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::OutOfRange {
+                field: "breaks.duration_secs".to_string(),
+                value: 1,
+                min: 5,
+                max: 1800,
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_breaks() {
+        // 旧版本的配置文件没有 breaks，使用默认值（关闭）
+        let config: AppConfig =
+            serde_json::from_value(json!({ "opacity": 0.5, "enabled": true, "auto_start": false }))
+                .unwrap();
+        assert_eq!(config.breaks, BreakConfig::default());
+        assert!(!config.breaks.enabled);
+        assert!(config.breaks.validate().is_ok());
+    }
+
+    #[test]
+    fn test_serialize_config_error() {
+        let error = ConfigError::UnknownLanguage { language: "xx".to_string() };
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["code"], "unknown_language");
+        assert_eq!(value["language"], "xx");
+    }
+
+    #[test]
+    fn test_find_preset() {
+        let config = AppConfig::default();
+        let preset = config.find_preset("reading").unwrap();
+        assert_eq!(preset.name, "Reading");
+        assert_eq!(preset.to_patch(), json!({ "opacity": 0.8f32 }));
+        assert!(config.find_preset("Gaming").is_none());
+    }
+
+    #[test]
+    fn test_next_preset() {
+        // 默认透明度 0.6 与 Coding 相同
+        let mut config = AppConfig::default();
+        assert_eq!(config.next_preset().unwrap().name, "Presentation");
+
+        config.opacity = 0.3;
+        assert_eq!(config.next_preset().unwrap().name, "Reading");
+
+        config.opacity = 0.45;
+        assert_eq!(config.next_preset().unwrap().name, "Reading");
+
+        config.presets.clear();
+        assert!(config.next_preset().is_none());
+    }
+
+    #[test]
+    fn test_merge_fields() {
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        let patch = json!({ "opacity": 0.4, "enabled": false });
+        merge_fields(&mut value, patch.as_object().unwrap()).unwrap();
+
+        let config: AppConfig = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(config.opacity, 0.4);
+        assert!(!config.enabled);
+        assert_eq!(config.language, "auto");
+
+        let patch = json!({ "brightness": 1 });
+        assert_eq!(
+            merge_fields(&mut value, patch.as_object().unwrap()),
+            Err(ConfigError::UnknownField { field: "brightness".to_string() })
+        );
+
+        // 嵌套对象只修改补丁中的字段
+        let patch = json!({ "hotkeys": { "toggle": "Ctrl+Shift+M" } });
+        merge_fields(&mut value, patch.as_object().unwrap()).unwrap();
+        let config: AppConfig = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(config.hotkeys.toggle, "Ctrl+Shift+M");
+        assert_eq!(config.hotkeys.lock_monitor, HotkeyConfig::default().lock_monitor);
+
+        let patch = json!({ "hotkeys": { "launch": "Ctrl+K" } });
+        assert_eq!(
+            merge_fields(&mut value, patch.as_object().unwrap()),
+            Err(ConfigError::UnknownField { field: "hotkeys.launch".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_load_layers() {
+        let root = std::env::temp_dir().join(format!("monofocus-layers-{}", std::process::id()));
+        let system_dir = root.join("system");
+        fs::create_dir_all(&system_dir).unwrap();
+        fs::write(system_dir.join("defaults.json"), r#"{"opacity": 0.8, "language": "en"}"#).unwrap();
+        fs::write(system_dir.join("policy.json"), r#"{"locked": {"enabled": true}}"#).unwrap();
+
+        let manager = ConfigManager {
+            config_path: root.join("config.json"),
+            system_dirs: vec![system_dir],
+            last_written: Mutex::new(None),
+        };
+        fs::write(manager.config_path(), r#"{"language": "ja", "enabled": false}"#).unwrap();
+
+        let config = manager.load();
+        assert_eq!(config.opacity, 0.8);
+        assert_eq!(config.language, "ja");
+        assert!(config.enabled);
+
+        let patch = json!({ "enabled": false });
+        assert_eq!(
+            manager.patch(&patch).unwrap_err(),
+            ConfigError::Locked { field: "enabled".to_string() }
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_patch_invalid_file() {
+        let root = std::env::temp_dir().join(format!("monofocus-invalid-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let manager = ConfigManager {
+            config_path: root.join("config.json"),
+            system_dirs: Vec::new(),
+            last_written: Mutex::new(None),
+        };
+
+        // 文件不存在时以默认值为基础
+        manager.patch(&json!({ "opacity": 0.5 })).unwrap();
+        assert_eq!(manager.load().opacity, 0.5);
+
+        // 文件无效时报错，不覆盖用户的文件
+        for content in [r#"{"opacity": 0.5, "language": "#, r#"{"opacity": 7}"#] {
+            fs::write(manager.config_path(), content).unwrap();
+            assert!(matches!(
+                manager.patch(&json!({ "enabled": false })),
+                Err(ConfigError::InvalidFile { .. })
+            ));
+            assert_eq!(fs::read_to_string(manager.config_path()).unwrap(), content);
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_take_own_write() {
+        let root = std::env::temp_dir().join(format!("monofocus-own-write-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let manager = ConfigManager {
+            config_path: root.join("config.json"),
+            system_dirs: Vec::new(),
+            last_written: Mutex::new(None),
+        };
+
+        // 程序自己的保存不需要重新加载，只跳过一次
+        manager.save(&AppConfig::default()).unwrap();
+        let saved = fs::read_to_string(manager.config_path()).unwrap();
+        assert!(!manager.take_own_write(r#"{"opacity": 0.5}"#));
+        assert!(manager.take_own_write(&saved));
+        assert!(!manager.take_own_write(&saved));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_write_schema() {
+        let root = std::env::temp_dir().join(format!("monofocus-schema-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("config.schema.json");
+
+        // 内容相同时不再写入
+        assert!(write_schema(&path).unwrap());
+        assert!(!write_schema(&path).unwrap());
+        fs::write(&path, "{}").unwrap();
+        assert!(write_schema(&path).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_config_schema() {
+        let schema = config_schema();
+        let languages = schema["properties"]["language"]["enum"].as_array().unwrap();
+        assert_eq!(languages.len(), SUPPORTED_LANGUAGES.len() + 1);
+        assert_eq!(schema["properties"]["language"]["default"], AUTO_LANGUAGE);
+        assert_eq!(
+            schema["properties"]["animation_duration"]["maximum"],
+            MAX_ANIMATION_DURATION
+        );
+    }
 }
 
@@ -0,0 +1,84 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// 启动配置文件监听线程，内容变化时以新的内容调用 `callback`
+///
+/// 监听的是配置目录而不是文件本身：很多编辑器和 dotfile 管理工具会先写临时文件再重命名，
+/// 直接监听文件会在第一次替换后失效。
+pub fn start_config_watcher<F>(config_path: PathBuf, callback: F) -> thread::JoinHandle<()>
+where
+    F: Fn(&str) + Send + 'static,
+{
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel();
+
+        let mut watcher: RecommendedWatcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(_) => return,
+        };
+
+        let config_dir = match config_path.parent() {
+            Some(dir) => dir.to_path_buf(),
+            None => return,
+        };
+
+        if watcher.watch(&config_dir, RecursiveMode::NonRecursive).is_err() {
+            return;
+        }
+
+        let mut last_content = fs::read_to_string(&config_path).ok();
+
+        while let Ok(event) = rx.recv() {
+            if !is_config_event(&event, &config_path) {
+                continue;
+            }
+
+            // 合并短时间内的连续事件（一次保存通常会产生多个事件）
+            while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+
+            // 只有内容真正变化时才触发回调
+            let Ok(content) = fs::read_to_string(&config_path) else {
+                continue;
+            };
+            if last_content.as_deref() == Some(content.as_str()) {
+                continue;
+            }
+            callback(&content);
+            last_content = Some(content);
+        }
+    })
+}
+
+/// 判断事件是否与配置文件有关
+fn is_config_event(event: &notify::Result<notify::Event>, config_path: &Path) -> bool {
+    match event {
+        Ok(event) => event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == config_path.file_name()),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{Event, EventKind, ModifyKind};
+
+    #[test]
+    fn test_is_config_event() {
+        let config_path = PathBuf::from("/tmp/MonoFocus/config.json");
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/tmp/MonoFocus/config.json"));
+        assert!(is_config_event(&Ok(event), &config_path));
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/tmp/MonoFocus/other.json"));
+        assert!(!is_config_event(&Ok(event), &config_path));
+    }
+}
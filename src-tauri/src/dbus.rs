@@ -0,0 +1,257 @@
+// D-Bus 会话总线接口，供桌面小部件和脚本集成
+//
+// 例如：
+//   busctl --user call org.monofocus.MonoFocus /org/monofocus/MonoFocus org.monofocus.MonoFocus Toggle
+//   busctl --user get-property org.monofocus.MonoFocus /org/monofocus/MonoFocus org.monofocus.MonoFocus Opacity
+
+use crate::actions::Controller;
+use crate::config::ConfigError;
+use std::collections::HashMap;
+use std::sync::Arc;
+use zbus::blocking::{Connection, ConnectionBuilder};
+use zbus::zvariant::Value;
+use zbus::{dbus_interface, fdo, SignalContext};
+
+/// 总线名称和接口名称
+pub const BUS_NAME: &str = "org.monofocus.MonoFocus";
+
+/// 对象路径
+pub const OBJECT_PATH: &str = "/org/monofocus/MonoFocus";
+
+/// D-Bus 对象，方法和属性都通过 Controller 读写应用状态
+struct MonoFocusObject {
+    controller: Arc<dyn Controller>,
+}
+
+#[dbus_interface(name = "org.monofocus.MonoFocus")]
+impl MonoFocusObject {
+    /// 切换护眼模式，返回切换后的状态
+    fn toggle(&self) -> fdo::Result<bool> {
+        self.controller.toggle().map_err(to_fdo_error)
+    }
+
+    /// 设置遮罩透明度（超出 0.0 - 1.0 时取最近的有效值，与其他控制接口一致）
+    fn set_opacity(&self, opacity: f64) -> fdo::Result<()> {
+        self.controller
+            .set_opacity(opacity as f32)
+            .map_err(to_fdo_error)
+    }
+
+    /// 应用预设（名称不区分大小写）
+    fn apply_preset(&self, name: &str) -> fdo::Result<()> {
+        self.controller.apply_preset(name).map_err(to_fdo_error)
+    }
+
+    #[dbus_interface(property)]
+    fn enabled(&self) -> bool {
+        self.controller.get_config().enabled
+    }
+
+    #[dbus_interface(property)]
+    fn opacity(&self) -> f64 {
+        self.controller.get_config().opacity as f64
+    }
+
+    /// 当前显示器 ID，尚未检测到时为空字符串
+    #[dbus_interface(property)]
+    fn active_monitor(&self) -> String {
+        self.controller.current_monitor().unwrap_or_default()
+    }
+
+    /// 当前显示器变化（由 DbusService::notify_monitor_changed 发送）
+    #[dbus_interface(signal)]
+    async fn monitor_changed(ctxt: &SignalContext<'_>, monitor_id: &str) -> zbus::Result<()>;
+}
+
+fn to_fdo_error(e: ConfigError) -> fdo::Error {
+    match e {
+        ConfigError::Io { .. } | ConfigError::InvalidFile { .. } => {
+            fdo::Error::Failed(e.to_string())
+        }
+        _ => fdo::Error::InvalidArgs(e.to_string()),
+    }
+}
+
+/// 已注册的 D-Bus 服务，作为 Tauri 状态保存，用于发送信号
+pub struct DbusService {
+    connection: Connection,
+    controller: Arc<dyn Controller>,
+}
+
+impl DbusService {
+    /// 在会话总线上注册服务
+    pub fn start(controller: Arc<dyn Controller>) -> zbus::Result<Self> {
+        Self::start_on(ConnectionBuilder::session()?, controller)
+    }
+
+    fn start_on(builder: ConnectionBuilder, controller: Arc<dyn Controller>) -> zbus::Result<Self> {
+        let object = MonoFocusObject {
+            controller: controller.clone(),
+        };
+
+        let connection = builder
+            .name(BUS_NAME)?
+            .serve_at(OBJECT_PATH, object)?
+            .build()?;
+
+        Ok(Self {
+            connection,
+            controller,
+        })
+    }
+
+    /// 配置变化后发送 PropertiesChanged
+    pub fn notify_config_changed(&self) {
+        let config = self.controller.get_config();
+
+        let mut changed = HashMap::new();
+        changed.insert("Enabled", Value::from(config.enabled));
+        changed.insert("Opacity", Value::from(config.opacity as f64));
+        self.emit_properties_changed(changed);
+    }
+
+    /// 当前显示器变化后发送 MonitorChanged 信号
+    pub fn notify_monitor_changed(&self, monitor_id: &str) {
+        let object_server = self.connection.object_server();
+        if let Ok(object) = object_server.interface::<_, MonoFocusObject>(OBJECT_PATH) {
+            let _ = zbus::block_on(MonoFocusObject::monitor_changed(
+                object.signal_context(),
+                monitor_id,
+            ));
+        }
+
+        let mut changed = HashMap::new();
+        changed.insert("ActiveMonitor", Value::from(monitor_id));
+        self.emit_properties_changed(changed);
+    }
+
+    fn emit_properties_changed(&self, changed: HashMap<&str, Value<'_>>) {
+        let invalidated: Vec<&str> = Vec::new();
+        let _ = self.connection.emit_signal(
+            None::<&str>,
+            OBJECT_PATH,
+            "org.freedesktop.DBus.Properties",
+            "PropertiesChanged",
+            &(BUS_NAME, changed, invalidated),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::mock::MockController;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use zbus::blocking::{Proxy, ProxyBuilder};
+    use zbus::CacheProperties;
+
+    /// 私有的 dbus-daemon 会话，测试结束时关闭
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        /// 系统中没有 dbus-daemon 时返回 None
+        fn start() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+
+            Some(Self {
+                daemon,
+                address: address.trim().to_string(),
+            })
+        }
+
+        fn connect(&self) -> ConnectionBuilder<'static> {
+            ConnectionBuilder::address(self.address.as_str()).unwrap()
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    fn proxy(connection: &Connection) -> Proxy<'static> {
+        ProxyBuilder::new_bare(connection)
+            .destination(BUS_NAME)
+            .unwrap()
+            .path(OBJECT_PATH)
+            .unwrap()
+            .interface(BUS_NAME)
+            .unwrap()
+            .cache_properties(CacheProperties::No)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_dbus_interface() {
+        let Some(bus) = PrivateBus::start() else {
+            eprintln!("dbus-daemon not found, skipping");
+            return;
+        };
+
+        let service =
+            DbusService::start_on(bus.connect(), Arc::new(MockController::default())).unwrap();
+        let client = bus.connect().build().unwrap();
+        let proxy = proxy(&client);
+
+        assert!(proxy.get_property::<bool>("Enabled").unwrap());
+        let enabled: bool = proxy.call("Toggle", &()).unwrap();
+        assert!(!enabled);
+        assert!(!proxy.get_property::<bool>("Enabled").unwrap());
+
+        proxy.call::<_, _, ()>("SetOpacity", &(0.4f64,)).unwrap();
+        let opacity = proxy.get_property::<f64>("Opacity").unwrap();
+        assert!((opacity - 0.4).abs() < 1e-6);
+        // 超出范围时取最近的有效值
+        proxy.call::<_, _, ()>("SetOpacity", &(1.5f64,)).unwrap();
+        assert_eq!(proxy.get_property::<f64>("Opacity").unwrap(), 1.0);
+
+        proxy
+            .call::<_, _, ()>("ApplyPreset", &("reading",))
+            .unwrap();
+        let opacity = proxy.get_property::<f64>("Opacity").unwrap();
+        assert!((opacity - 0.8).abs() < 1e-6);
+        assert!(proxy.call::<_, _, ()>("ApplyPreset", &("Gaming",)).is_err());
+
+        assert_eq!(
+            proxy.get_property::<String>("ActiveMonitor").unwrap(),
+            "monitor_0"
+        );
+
+        let xml: String = client
+            .call_method(
+                Some(BUS_NAME),
+                OBJECT_PATH,
+                Some("org.freedesktop.DBus.Introspectable"),
+                "Introspect",
+                &(),
+            )
+            .unwrap()
+            .body()
+            .unwrap();
+        assert!(xml.contains("<signal name=\"MonitorChanged\">"));
+
+        // 订阅后再发送信号
+        let mut signals = proxy.receive_signal("MonitorChanged").unwrap();
+        service.notify_monitor_changed("monitor_1");
+        let message = signals.next().unwrap();
+        let (monitor_id,): (String,) = message.body().unwrap();
+        assert_eq!(monitor_id, "monitor_1");
+    }
+}
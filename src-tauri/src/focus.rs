@@ -0,0 +1,452 @@
+// 专注时段（番茄钟）：专注阶段加深非活跃显示器的遮罩（可选锁定当前显示器），结束后进入休息阶段
+//
+// 时段状态只保存在后端内存中；完成的专注阶段追加到配置目录下的 focus-sessions.jsonl（每行一条记录）。
+
+use crate::actions;
+use crate::config::{AppConfig, FocusConfig};
+use crate::i18n::{t, tf};
+use crate::monitor::get_monitors;
+use crate::overlay::OverlayConfig;
+use crate::pause::{now_secs, remaining_minutes};
+use crate::tray;
+use crate::AppState;
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 检查阶段是否到期的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 专注记录文件名（与 config.json 放在同一目录）
+pub const SESSION_LOG: &str = "focus-sessions.jsonl";
+
+/// 专注时段的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusPhase {
+    Focus,
+    Break,
+}
+
+/// 阶段到期后的变化
+#[derive(Debug, Clone, PartialEq)]
+pub enum FocusTransition {
+    /// 专注阶段完成，进入休息（附带需要记录的专注阶段）
+    BreakStarted(SessionRecord),
+    /// 休息结束，时段结束
+    Finished,
+}
+
+/// 进行中的专注时段
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusSession {
+    pub phase: FocusPhase,
+    pub started_at: u64,
+    pub phase_ends_at: u64,
+    break_minutes: u64,
+    /// 开始时由专注时段锁定的显示器，专注阶段结束时解除锁定
+    locked_monitor: Option<String>,
+}
+
+impl FocusSession {
+    pub fn new(config: &FocusConfig, now: u64, locked_monitor: Option<String>) -> Self {
+        Self {
+            phase: FocusPhase::Focus,
+            started_at: now,
+            phase_ends_at: now + config.focus_minutes * 60,
+            break_minutes: config.break_minutes,
+            locked_monitor,
+        }
+    }
+
+    /// 当前阶段到期时进入下一阶段
+    pub fn advance(&mut self, now: u64) -> Option<FocusTransition> {
+        if now < self.phase_ends_at {
+            return None;
+        }
+
+        match self.phase {
+            FocusPhase::Focus => {
+                let record = SessionRecord {
+                    started_at: self.started_at,
+                    ended_at: self.phase_ends_at,
+                    minutes: (self.phase_ends_at - self.started_at) / 60,
+                    monitor: self.locked_monitor.clone(),
+                };
+                self.phase = FocusPhase::Break;
+                self.phase_ends_at = now + self.break_minutes * 60;
+                Some(FocusTransition::BreakStarted(record))
+            }
+            FocusPhase::Break => Some(FocusTransition::Finished),
+        }
+    }
+
+    pub fn remaining_secs(&self, now: u64) -> u64 {
+        self.phase_ends_at.saturating_sub(now)
+    }
+}
+
+/// 完成的专注阶段（专注记录中的一行）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// 开始和结束时间（Unix 时间戳，秒）
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub minutes: u64,
+    /// 锁定的显示器
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+}
+
+/// 追加一条专注记录
+pub fn append_record(path: &Path, record: &SessionRecord) -> io::Result<()> {
+    let line = serde_json::to_string(record)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// 读取专注记录，跳过无法解析的行
+pub fn read_records(path: &Path) -> Vec<SessionRecord> {
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 今天（本地时间）完成的专注阶段数
+fn completed_today(records: &[SessionRecord], now: u64) -> usize {
+    let Some(today) = Local.timestamp_opt(now as i64, 0).single().map(|t| t.date_naive()) else {
+        return 0;
+    };
+    records
+        .iter()
+        .filter(|record| {
+            Local
+                .timestamp_opt(record.ended_at as i64, 0)
+                .single()
+                .map(|t| t.date_naive())
+                == Some(today)
+        })
+        .count()
+}
+
+/// 专注状态（focus-session-changed 事件的内容）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusStatus {
+    pub active: bool,
+    pub phase: Option<FocusPhase>,
+    /// 当前阶段的剩余秒数
+    pub remaining_secs: Option<u64>,
+    /// 当前阶段的结束时间（Unix 时间戳，秒）
+    pub ends_at: Option<u64>,
+    /// 今天完成的专注阶段数
+    pub completed_today: usize,
+}
+
+/// 专注时段状态
+#[derive(Default)]
+pub struct FocusState {
+    session: Mutex<Option<FocusSession>>,
+}
+
+fn current_session(app: &AppHandle) -> Option<FocusSession> {
+    app.try_state::<FocusState>()
+        .and_then(|state| state.session.lock().unwrap().clone())
+}
+
+/// 是否有进行中的专注时段
+pub fn is_active(app: &AppHandle) -> bool {
+    current_session(app).is_some()
+}
+
+/// 当前阶段和剩余分钟数（托盘显示）
+pub fn phase_remaining(app: &AppHandle) -> Option<(FocusPhase, u64)> {
+    current_session(app).map(|session| {
+        (session.phase, remaining_minutes(session.remaining_secs(now_secs())))
+    })
+}
+
+/// 专注阶段加深遮罩：透明度至少为 focus.opacity（blackout 时为不透明的黑色）
+pub fn apply_overlay(app: &AppHandle, config: &AppConfig, overlay: &mut OverlayConfig) {
+    let phase = current_session(app).map(|session| session.phase);
+    focus_overlay(phase, config, overlay);
+}
+
+/// 只加深已经显示的遮罩：护眼模式关闭、暂停或被管理员锁定为关闭时专注时段不显示遮罩
+fn focus_overlay(phase: Option<FocusPhase>, config: &AppConfig, overlay: &mut OverlayConfig) {
+    if phase != Some(FocusPhase::Focus) || !overlay.enabled || config.paused_until.is_some() {
+        return;
+    }
+
+    overlay.gradual = None;
+    overlay.recent = None;
+    if config.focus.blackout {
+        overlay.opacity = 1.0;
+        overlay.color = [0, 0, 0];
+    } else {
+        overlay.opacity = overlay.opacity.max(config.focus.opacity);
+    }
+}
+
+fn log_path(app: &AppHandle) -> PathBuf {
+    app.state::<AppState>()
+        .config_manager
+        .lock()
+        .unwrap()
+        .config_path()
+        .with_file_name(SESSION_LOG)
+}
+
+/// 当前的专注状态
+pub fn focus_status(app: &AppHandle) -> FocusStatus {
+    let now = now_secs();
+    let session = current_session(app);
+    FocusStatus {
+        active: session.is_some(),
+        phase: session.as_ref().map(|s| s.phase),
+        remaining_secs: session.as_ref().map(|s| s.remaining_secs(now)),
+        ends_at: session.as_ref().map(|s| s.phase_ends_at),
+        completed_today: completed_today(&read_records(&log_path(app)), now),
+    }
+}
+
+/// 开始专注时段（已有进行中的时段时不做修改）
+pub fn start_session(app: &AppHandle) -> FocusStatus {
+    let Some(state) = app.try_state::<FocusState>() else {
+        return focus_status(app);
+    };
+    if state.session.lock().unwrap().is_some() {
+        return focus_status(app);
+    }
+
+    let config = actions::load_config(app);
+
+    // 正在进行的休息提醒让位于专注时段
+    crate::breaks::skip_break(app);
+
+    // 只在没有手动锁定时由专注时段锁定，结束时解除
+    let mut locked_monitor = None;
+    if config.focus.lock_monitor {
+        let app_state = app.state::<AppState>();
+        let already_locked = app_state.locked_monitor_id.lock().unwrap().is_some();
+        let current = app_state.current_monitor_id.lock().unwrap().clone();
+        if !already_locked {
+            if let Some(monitor_id) = current {
+                locked_monitor = actions::set_focus_lock(app, Some(monitor_id));
+            }
+        }
+    }
+
+    *state.session.lock().unwrap() =
+        Some(FocusSession::new(&config.focus, now_secs(), locked_monitor));
+    session_changed(app, &config)
+}
+
+/// 结束专注时段（未完成的专注阶段不记录）
+pub fn stop_session(app: &AppHandle) -> FocusStatus {
+    let session = app
+        .try_state::<FocusState>()
+        .and_then(|state| state.session.lock().unwrap().take());
+
+    if let Some(session) = session {
+        match session.phase {
+            FocusPhase::Focus => release_lock(app, &session),
+            FocusPhase::Break => hide_break(app),
+        }
+    }
+    session_changed(app, &actions::load_config(app))
+}
+
+/// 解除专注时段锁定的显示器（之后手动切换了锁定时保持不变）
+fn release_lock(app: &AppHandle, session: &FocusSession) {
+    let Some(monitor_id) = &session.locked_monitor else {
+        return;
+    };
+    let locked = app.state::<AppState>().locked_monitor_id.lock().unwrap().clone();
+    if locked.as_ref() == Some(monitor_id) {
+        actions::set_focus_lock(app, None);
+    }
+}
+
+fn with_overlay_manager(app: &AppHandle, f: impl FnOnce(&crate::overlay::OverlayManager)) {
+    let state = app.state::<AppState>();
+    let overlay_manager = state.overlay_manager.lock().unwrap();
+    if let Some(overlay_manager) = overlay_manager.as_ref() {
+        f(overlay_manager);
+    }
+}
+
+fn hide_break(app: &AppHandle) {
+    with_overlay_manager(app, |manager| manager.hide_break());
+}
+
+/// 更新遮罩和托盘，通知前端，返回新的状态
+fn session_changed(app: &AppHandle, config: &AppConfig) -> FocusStatus {
+    actions::refresh_overlays(app, config);
+    tray::refresh_tray(app, config);
+
+    let status = focus_status(app);
+    let _ = app.emit_all("focus-session-changed", &status);
+    status
+}
+
+/// 进入下一阶段：记录完成的专注阶段并显示休息遮罩，或结束时段
+fn handle_transition(app: &AppHandle, session: &FocusSession, transition: FocusTransition) {
+    let config = actions::load_config(app);
+
+    match transition {
+        FocusTransition::BreakStarted(record) => {
+            if let Err(e) = append_record(&log_path(app), &record) {
+                eprintln!("Failed to write focus session log: {}", e);
+            }
+            release_lock(app, session);
+
+            let language = config.language.as_str();
+            let title = t(language, "focus.breakTitle");
+            let hint = tf(
+                language,
+                "focus.breakHint",
+                &[("minutes", session.break_minutes.to_string())],
+            );
+            let remaining = session.remaining_secs(now_secs());
+            with_overlay_manager(app, |manager| {
+                manager.show_break(&get_monitors(), config.breaks.opacity, remaining, &title, &hint)
+            });
+        }
+        FocusTransition::Finished => {
+            if let Some(state) = app.try_state::<FocusState>() {
+                *state.session.lock().unwrap() = None;
+            }
+            hide_break(app);
+        }
+    }
+
+    session_changed(app, &config);
+}
+
+/// 启动专注计时线程：阶段到期时切换，休息阶段更新倒计时，剩余分钟数变化时刷新托盘
+pub fn start_focus_timer(app: AppHandle) {
+    thread::spawn(move || {
+        let mut last_remaining = None;
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let Some(state) = app.try_state::<FocusState>() else {
+                continue;
+            };
+
+            let now = now_secs();
+            let (session, transition) = {
+                let mut session = state.session.lock().unwrap();
+                let transition = session.as_mut().and_then(|s| s.advance(now));
+                (session.clone(), transition)
+            };
+
+            if let (Some(session), Some(transition)) = (&session, transition) {
+                handle_transition(&app, session, transition);
+            } else if let Some(session) = session.as_ref().filter(|s| s.phase == FocusPhase::Break) {
+                let remaining = session.remaining_secs(now);
+                with_overlay_manager(&app, |manager| manager.update_break(remaining));
+            }
+
+            let phase_remaining = phase_remaining(&app);
+            if phase_remaining != last_remaining {
+                last_remaining = phase_remaining;
+                tray::refresh_tray(&app, &actions::load_config(&app));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_phases() {
+        let config = FocusConfig::default();
+        let mut session = FocusSession::new(&config, 1000, Some("monitor_1".to_string()));
+        assert_eq!(session.remaining_secs(1000), 25 * 60);
+        assert_eq!(session.advance(1000 + 60), None);
+
+        let end = 1000 + 25 * 60;
+        assert_eq!(
+            session.advance(end + 2),
+            Some(FocusTransition::BreakStarted(SessionRecord {
+                started_at: 1000,
+                ended_at: end,
+                minutes: 25,
+                monitor: Some("monitor_1".to_string()),
+            }))
+        );
+        assert_eq!(session.phase, FocusPhase::Break);
+        assert_eq!(session.remaining_secs(end + 2), 5 * 60);
+
+        assert_eq!(session.advance(end + 60), None);
+        assert_eq!(session.advance(end + 2 + 5 * 60), Some(FocusTransition::Finished));
+    }
+
+    #[test]
+    fn test_focus_overlay() {
+        let config = AppConfig::default();
+        let mut overlay = OverlayConfig { opacity: 0.3, ..Default::default() };
+        focus_overlay(Some(FocusPhase::Focus), &config, &mut overlay);
+        assert_eq!(overlay.opacity, config.focus.opacity);
+
+        // 暂停期间专注时段不显示遮罩
+        let paused = AppConfig {
+            enabled: false,
+            paused_until: Some(now_secs() + 60),
+            ..Default::default()
+        };
+        let mut overlay = OverlayConfig { enabled: false, ..Default::default() };
+        focus_overlay(Some(FocusPhase::Focus), &paused, &mut overlay);
+        assert!(!overlay.enabled);
+
+        let mut overlay = OverlayConfig { opacity: 0.3, ..Default::default() };
+        focus_overlay(Some(FocusPhase::Break), &config, &mut overlay);
+        assert_eq!(overlay.opacity, 0.3);
+    }
+
+    #[test]
+    fn test_session_log() {
+        let dir = std::env::temp_dir().join(format!("monofocus-focus-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SESSION_LOG);
+        let _ = fs::remove_file(&path);
+
+        assert!(read_records(&path).is_empty());
+
+        let now = now_secs();
+        let record = SessionRecord {
+            started_at: now - 25 * 60,
+            ended_at: now,
+            minutes: 25,
+            monitor: None,
+        };
+        append_record(&path, &record).unwrap();
+        append_record(&path, &SessionRecord { ended_at: now - 3 * 24 * 60 * 60, ..record.clone() }).unwrap();
+
+        // 损坏的行不影响其他记录
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "not json").unwrap();
+
+        let records = read_records(&path);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], record);
+        assert_eq!(completed_today(&records, now), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
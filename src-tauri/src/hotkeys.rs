@@ -0,0 +1,266 @@
+// 全局快捷键：在后端注册，不依赖窗口是否获得焦点
+
+use crate::actions;
+use crate::config::{ConfigError, HotkeyConfig};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, GlobalShortcutManager, Manager};
+
+/// 快捷键对应的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    Toggle,
+    OpacityUp,
+    OpacityDown,
+    LockMonitor,
+    CyclePreset,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 5] = [
+        HotkeyAction::Toggle,
+        HotkeyAction::OpacityUp,
+        HotkeyAction::OpacityDown,
+        HotkeyAction::LockMonitor,
+        HotkeyAction::CyclePreset,
+    ];
+
+    /// 与 HotkeyConfig 中的字段名一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HotkeyAction::Toggle => "toggle",
+            HotkeyAction::OpacityUp => "opacity_up",
+            HotkeyAction::OpacityDown => "opacity_down",
+            HotkeyAction::LockMonitor => "lock_monitor",
+            HotkeyAction::CyclePreset => "cycle_preset",
+        }
+    }
+
+    /// 该操作绑定的快捷键（空字符串表示未绑定）
+    pub fn accelerator<'a>(&self, hotkeys: &'a HotkeyConfig) -> &'a str {
+        match self {
+            HotkeyAction::Toggle => &hotkeys.toggle,
+            HotkeyAction::OpacityUp => &hotkeys.opacity_up,
+            HotkeyAction::OpacityDown => &hotkeys.opacity_down,
+            HotkeyAction::LockMonitor => &hotkeys.lock_monitor,
+            HotkeyAction::CyclePreset => &hotkeys.cycle_preset,
+        }
+    }
+
+    fn run(&self, app: &AppHandle) {
+        let result = match self {
+            HotkeyAction::Toggle => actions::toggle(app).map(|_| ()),
+            HotkeyAction::OpacityUp => actions::adjust_opacity(app, 1.0),
+            HotkeyAction::OpacityDown => actions::adjust_opacity(app, -1.0),
+            HotkeyAction::LockMonitor => {
+                actions::toggle_focus_lock(app);
+                Ok(())
+            }
+            HotkeyAction::CyclePreset => actions::cycle_preset(app),
+        };
+        // 与托盘一样提示失败原因（例如设置被管理员锁定）
+        if let Err(e) = result {
+            actions::report_error(app, &e);
+        }
+    }
+}
+
+/// 快捷键的注册状态（提供给前端显示）
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyStatus {
+    pub action: HotkeyAction,
+    pub accelerator: String,
+    pub registered: bool,
+    /// 注册失败的原因，例如已被其他程序占用
+    pub error: Option<String>,
+}
+
+/// 当前已注册的快捷键
+#[derive(Default)]
+pub struct HotkeyState {
+    registered: Mutex<Option<HotkeyConfig>>,
+    statuses: Mutex<Vec<HotkeyStatus>>,
+}
+
+const MODIFIERS: &[&str] = &["ctrl", "alt", "shift", "super"];
+
+const NAMED_KEYS: &[&str] = &[
+    "up", "down", "left", "right", "space", "tab", "enter", "backspace", "delete", "insert",
+    "home", "end", "pageup", "pagedown", "escape", "plus", "minus", "equal", "comma", "period",
+];
+
+/// 规范化快捷键（修饰键排序、统一别名、忽略大小写），用于冲突检测
+///
+/// 格式无效时返回 None。
+pub fn normalize_accelerator(accelerator: &str) -> Option<String> {
+    let parts: Vec<String> = accelerator
+        .split('+')
+        .map(|part| part.trim().to_lowercase())
+        .collect();
+    let (key, modifiers) = parts.split_last()?;
+
+    let mut normalized: Vec<&str> = Vec::new();
+    for modifier in modifiers {
+        let modifier = match modifier.as_str() {
+            "ctrl" | "control" => "ctrl",
+            "alt" | "option" => "alt",
+            "shift" => "shift",
+            "super" | "cmd" | "command" | "meta" => "super",
+            "cmdorctrl" | "commandorcontrol" => {
+                if cfg!(target_os = "macos") {
+                    "super"
+                } else {
+                    "ctrl"
+                }
+            }
+            _ => return None,
+        };
+        if !normalized.contains(&modifier) {
+            normalized.push(modifier);
+        }
+    }
+    normalized.sort_by_key(|modifier| MODIFIERS.iter().position(|m| m == modifier));
+
+    let valid_key = match key.as_str() {
+        key if key.chars().count() == 1 => key.chars().all(|c| c.is_ascii_graphic()),
+        key if NAMED_KEYS.contains(&key) => true,
+        key => key
+            .strip_prefix('f')
+            .and_then(|n| n.parse::<u8>().ok())
+            .is_some_and(|n| (1..=24).contains(&n)),
+    };
+    if !valid_key {
+        return None;
+    }
+
+    normalized.push(key);
+    Some(normalized.join("+"))
+}
+
+/// 校验快捷键格式，并检查是否有两个操作绑定了同一个快捷键
+pub fn validate_hotkeys(hotkeys: &HotkeyConfig) -> Result<(), ConfigError> {
+    let mut seen: Vec<(String, HotkeyAction)> = Vec::new();
+
+    for action in HotkeyAction::ALL {
+        let accelerator = action.accelerator(hotkeys);
+        if accelerator.is_empty() {
+            continue;
+        }
+
+        let normalized =
+            normalize_accelerator(accelerator).ok_or_else(|| ConfigError::InvalidHotkey {
+                action: action.as_str().to_string(),
+                accelerator: accelerator.to_string(),
+            })?;
+
+        if let Some((_, other)) = seen.iter().find(|(existing, _)| *existing == normalized) {
+            return Err(ConfigError::HotkeyConflict {
+                accelerator: accelerator.to_string(),
+                actions: vec![other.as_str().to_string(), action.as_str().to_string()],
+            });
+        }
+        seen.push((normalized, action));
+    }
+
+    Ok(())
+}
+
+/// 按配置注册全局快捷键，配置未变化时不重复注册
+pub fn register_hotkeys(app: &AppHandle, hotkeys: &HotkeyConfig) {
+    let Some(state) = app.try_state::<HotkeyState>() else {
+        return;
+    };
+
+    let mut registered = state.registered.lock().unwrap();
+    if registered.as_ref() == Some(hotkeys) {
+        return;
+    }
+
+    let mut manager = app.global_shortcut_manager();
+    let _ = manager.unregister_all();
+
+    let mut statuses = Vec::new();
+    for action in HotkeyAction::ALL {
+        let accelerator = action.accelerator(hotkeys);
+        if accelerator.is_empty() {
+            continue;
+        }
+
+        let handle = app.clone();
+        let result = manager.register(accelerator, move || action.run(&handle));
+
+        statuses.push(HotkeyStatus {
+            action,
+            accelerator: accelerator.to_string(),
+            registered: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+        });
+    }
+
+    *registered = Some(hotkeys.clone());
+    *state.statuses.lock().unwrap() = statuses;
+}
+
+/// 当前快捷键的注册状态
+pub fn hotkey_statuses(app: &AppHandle) -> Vec<HotkeyStatus> {
+    app.try_state::<HotkeyState>()
+        .map(|state| state.statuses.lock().unwrap().clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_accelerator() {
+        assert_eq!(
+            normalize_accelerator("Shift+Control+M"),
+            normalize_accelerator("ctrl+shift+m")
+        );
+        assert_eq!(
+            normalize_accelerator("Alt+Option+Up").as_deref(),
+            Some("alt+up")
+        );
+        assert_eq!(normalize_accelerator("Ctrl+F12").as_deref(), Some("ctrl+f12"));
+        assert!(normalize_accelerator("Ctrl+").is_none());
+        assert!(normalize_accelerator("Hyper+M").is_none());
+        assert!(normalize_accelerator("Ctrl+F25").is_none());
+    }
+
+    #[test]
+    fn test_validate_hotkeys() {
+        assert!(validate_hotkeys(&HotkeyConfig::default()).is_ok());
+
+        let hotkeys = HotkeyConfig {
+            toggle: "Ctrl+Alt+M".to_string(),
+            cycle_preset: "alt+control+m".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_hotkeys(&hotkeys),
+            Err(ConfigError::HotkeyConflict {
+                accelerator: "alt+control+m".to_string(),
+                actions: vec!["toggle".to_string(), "cycle_preset".to_string()],
+            })
+        );
+
+        let hotkeys = HotkeyConfig {
+            lock_monitor: "Ctrl+Nope".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            validate_hotkeys(&hotkeys),
+            Err(ConfigError::InvalidHotkey { .. })
+        ));
+
+        // 空字符串表示未绑定，不参与冲突检测
+        let hotkeys = HotkeyConfig {
+            opacity_up: String::new(),
+            opacity_down: String::new(),
+            ..Default::default()
+        };
+        assert!(validate_hotkeys(&hotkeys).is_ok());
+    }
+}
@@ -0,0 +1,291 @@
+// 界面文本目录：托盘、后端错误消息和前端界面共用 locales/*.json
+//
+// 每种语言一个扁平的 JSON 对象（键 → 文本），文本中的 {name} 为占位符。
+// 新增语言时添加 JSON 文件，并在 CATALOGS 和 SUPPORTED_LANGUAGES 中登记。
+
+use crate::config::{ConfigError, AUTO_LANGUAGE, SUPPORTED_LANGUAGES};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// 内置的文本目录（编译时嵌入）
+const CATALOGS: &[(&str, &str)] = &[
+    ("zh", include_str!("../locales/zh.json")),
+    ("en", include_str!("../locales/en.json")),
+    ("ja", include_str!("../locales/ja.json")),
+    ("fr", include_str!("../locales/fr.json")),
+    ("de", include_str!("../locales/de.json")),
+    ("es", include_str!("../locales/es.json")),
+];
+
+/// 缺少翻译时最终使用的语言
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+type Messages = HashMap<String, String>;
+
+fn catalogs() -> &'static HashMap<&'static str, Messages> {
+    static CATALOGS_CACHE: OnceLock<HashMap<&'static str, Messages>> = OnceLock::new();
+    CATALOGS_CACHE.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(language, source)| {
+                let messages = serde_json::from_str(source)
+                    .unwrap_or_else(|e| panic!("invalid locale catalog {}: {}", language, e));
+                (*language, messages)
+            })
+            .collect()
+    })
+}
+
+/// 系统语言（启动后第一次使用时检测）
+pub fn system_language() -> &'static str {
+    static SYSTEM_LANGUAGE: OnceLock<&'static str> = OnceLock::new();
+    SYSTEM_LANGUAGE.get_or_init(|| language_from_env(|name| std::env::var(name).ok()))
+}
+
+/// 按 POSIX 的优先级读取 LC_ALL、LC_MESSAGES、LANG，映射到支持的语言
+///
+/// 例如 "de_AT.UTF-8" → "de"；"C"、"POSIX" 或不支持的语言使用 FALLBACK_LANGUAGE。
+fn language_from_env(var: impl Fn(&str) -> Option<String>) -> &'static str {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())
+        .and_then(|locale| supported_language(&locale))
+        .unwrap_or(FALLBACK_LANGUAGE)
+}
+
+/// 将 locale（如 "zh_CN.UTF-8"、"fr_FR@euro"、"ja"）映射到支持的语言
+fn supported_language(locale: &str) -> Option<&'static str> {
+    let primary = locale
+        .split(['.', '@'])
+        .next()?
+        .split(['_', '-'])
+        .next()?
+        .to_lowercase();
+    SUPPORTED_LANGUAGES
+        .iter()
+        .find(|language| **language == primary)
+        .copied()
+}
+
+/// "auto" 解析为系统语言，其他值原样返回
+pub fn resolve_language(language: &str) -> &str {
+    if language == AUTO_LANGUAGE {
+        system_language()
+    } else {
+        language
+    }
+}
+
+/// 查找顺序：指定语言（"auto" 为系统语言）→ 主语言（如 "zh-TW" → "zh"）→ FALLBACK_LANGUAGE
+pub fn fallback_chain(language: &str) -> Vec<String> {
+    let language = resolve_language(language);
+    let mut chain = vec![language.to_string()];
+    if let Some((primary, _)) = language.split_once(['-', '_']) {
+        chain.push(primary.to_string());
+    }
+    chain.push(FALLBACK_LANGUAGE.to_string());
+    chain.dedup();
+    chain
+}
+
+/// 获取文本，所有语言都缺少时返回键本身
+pub fn t(language: &str, key: &str) -> String {
+    let catalogs = catalogs();
+    fallback_chain(language)
+        .iter()
+        .find_map(|lang| catalogs.get(lang.as_str())?.get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// 获取文本并替换 {name} 占位符
+pub fn tf(language: &str, key: &str, args: &[(&str, String)]) -> String {
+    let mut text = t(language, key);
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+/// 指定语言的完整目录（已按回退顺序补全），提供给前端
+pub fn bundle(language: &str) -> Messages {
+    let catalogs = catalogs();
+    let mut messages = Messages::new();
+    for lang in fallback_chain(language).iter().rev() {
+        if let Some(catalog) = catalogs.get(lang.as_str()) {
+            messages.extend(catalog.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    }
+    messages
+}
+
+/// 本地化的配置错误消息
+///
+/// 键为 "error.<code>"，错误的其余字段作为占位符（数组用 ", " 连接）。
+pub fn error_message(language: &str, error: &ConfigError) -> String {
+    let Ok(Value::Object(fields)) = serde_json::to_value(error) else {
+        return error.to_string();
+    };
+    let code = fields.get("code").and_then(Value::as_str).unwrap_or_default();
+
+    let args: Vec<(&str, String)> = fields
+        .iter()
+        .filter(|(name, _)| name.as_str() != "code")
+        .map(|(name, value)| (name.as_str(), placeholder_value(value)))
+        .collect();
+    tf(language, &format!("error.{}", code), &args)
+}
+
+fn placeholder_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(placeholder_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_match_supported_languages() {
+        let mut languages: Vec<&str> = CATALOGS.iter().map(|(language, _)| *language).collect();
+        let mut supported = SUPPORTED_LANGUAGES.to_vec();
+        languages.sort();
+        supported.sort();
+        assert_eq!(languages, supported);
+    }
+
+    #[test]
+    fn test_catalogs_complete() {
+        let reference = &catalogs()[FALLBACK_LANGUAGE];
+
+        for (language, catalog) in catalogs() {
+            let missing: Vec<_> = reference.keys().filter(|k| !catalog.contains_key(*k)).collect();
+            let extra: Vec<_> = catalog.keys().filter(|k| !reference.contains_key(*k)).collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", language, missing);
+            assert!(extra.is_empty(), "{} has unknown keys {:?}", language, extra);
+
+            for (key, text) in catalog {
+                assert!(!text.trim().is_empty(), "{}: {} is empty", language, key);
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(&reference[key]),
+                    "{}: placeholders of {} differ",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_error_has_message() {
+        let errors = [
+            ConfigError::UnknownLanguage { language: "xx".to_string() },
+            ConfigError::DurationOutOfRange { duration: 1, max: 2 },
+            ConfigError::DelayOutOfRange { delay: 1, max: 2 },
+            ConfigError::InvalidTheme { theme: "x".to_string() },
+            ConfigError::OpacityOutOfRange { opacity: 2.0 },
+            ConfigError::UnknownField { field: "x".to_string() },
+            ConfigError::UnknownPreset { name: "x".to_string() },
+            ConfigError::UnknownMonitor { monitor: "x".to_string() },
+            ConfigError::Locked { field: "x".to_string() },
+            ConfigError::OpacityBelowMinimum { opacity: 0.1, min: 0.2 },
+            ConfigError::InvalidHotkey { action: "x".to_string(), accelerator: "y".to_string() },
+            ConfigError::HotkeyConflict { accelerator: "x".to_string(), actions: Vec::new() },
+            ConfigError::OpacityStepOutOfRange { step: 1.0 },
+            ConfigError::OutOfRange { field: "x".to_string(), value: 1, min: 2, max: 3 },
+            ConfigError::InvalidValue { message: "x".to_string() },
+            ConfigError::InvalidFile { message: "x".to_string() },
+            ConfigError::Io { message: "x".to_string() },
+        ];
+
+        for error in &errors {
+            let message = error_message(FALLBACK_LANGUAGE, error);
+            assert!(!message.starts_with("error."), "no message for {:?}", error);
+            assert!(!message.contains('{'), "unfilled placeholder in {}", message);
+        }
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = ConfigError::HotkeyConflict {
+            accelerator: "Ctrl+M".to_string(),
+            actions: vec!["toggle".to_string(), "cycle_preset".to_string()],
+        };
+        assert_eq!(
+            error_message("en", &error),
+            "Shortcut Ctrl+M is used by toggle, cycle_preset"
+        );
+        assert_eq!(
+            error_message("zh", &ConfigError::UnknownPreset { name: "Gaming".to_string() }),
+            "未知的预设：Gaming"
+        );
+    }
+
+    #[test]
+    fn test_language_from_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(language_from_env(env(&[("LANG", "de_AT.UTF-8")])), "de");
+        assert_eq!(
+            language_from_env(env(&[("LANG", "zh_CN.UTF-8"), ("LC_MESSAGES", "ja_JP.UTF-8")])),
+            "ja"
+        );
+        // LC_ALL 优先，空值跳过
+        assert_eq!(
+            language_from_env(env(&[("LC_ALL", "fr_FR@euro"), ("LC_MESSAGES", "es_ES")])),
+            "fr"
+        );
+        assert_eq!(language_from_env(env(&[("LC_ALL", ""), ("LANG", "es_MX")])), "es");
+        assert_eq!(language_from_env(env(&[("LANG", "C.UTF-8")])), FALLBACK_LANGUAGE);
+        assert_eq!(language_from_env(env(&[("LANG", "pt_BR.UTF-8")])), FALLBACK_LANGUAGE);
+        assert_eq!(language_from_env(env(&[])), FALLBACK_LANGUAGE);
+    }
+
+    #[test]
+    fn test_resolve_language() {
+        assert_eq!(resolve_language("ja"), "ja");
+        assert!(SUPPORTED_LANGUAGES.contains(&resolve_language(AUTO_LANGUAGE)));
+        assert!(!bundle(AUTO_LANGUAGE).is_empty());
+    }
+
+    #[test]
+    fn test_fallback() {
+        assert_eq!(fallback_chain("zh-TW"), vec!["zh-TW", "zh", "en"]);
+        assert_eq!(fallback_chain("en"), vec!["en"]);
+
+        assert_eq!(t("de_AT", "tray.quit"), "Beenden");
+        assert_eq!(t("xx", "tray.quit"), "Exit");
+        assert_eq!(t("zh", "no.such.key"), "no.such.key");
+        assert_eq!(
+            tf("en", "tray.togglePaused", &[("remaining", "5 min".to_string())]),
+            "Eye Care Mode: PAUSED (5 min left)"
+        );
+
+        let bundle = bundle("xx");
+        assert_eq!(bundle.len(), catalogs()[FALLBACK_LANGUAGE].len());
+    }
+}
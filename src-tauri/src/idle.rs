@@ -0,0 +1,232 @@
+// 用户空闲时间：距离最后一次键盘或鼠标输入的秒数
+//
+// Linux 使用 X11 的 MIT-SCREEN-SAVER 扩展，Windows 使用 GetLastInputInfo，
+// macOS 使用 CGEventSourceSecondsSinceLastEventType。
+//
+// 离开超过 idle_dim_minutes 后所有显示器（包括当前显示器）显示遮罩；
+// 鼠标移动由鼠标监听线程立即恢复，键盘输入由这里的轮询恢复。
+//
+// 只有空闲监听线程查询系统（Linux 上保持一个 X11 连接），结果保存在 IdleState 中供休息提醒共用。
+
+use crate::actions;
+use crate::AppState;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 检查空闲时间的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 按空闲时间决定是否变暗，状态需要变化时返回新的状态
+///
+/// `threshold_secs` 为 0 表示关闭。
+fn idle_transition(dimmed: bool, idle_secs: u64, threshold_secs: u64) -> Option<bool> {
+    let idle = threshold_secs > 0 && idle_secs >= threshold_secs;
+    (idle != dimmed).then_some(idle)
+}
+
+/// 最近一次采样的空闲秒数
+#[derive(Default)]
+pub struct IdleState {
+    seconds: Mutex<Option<u64>>,
+}
+
+/// 最近一次采样的空闲秒数，无法获取或未采样时返回 0（按未空闲处理）
+pub fn idle_seconds(app: &AppHandle) -> u64 {
+    app.try_state::<IdleState>()
+        .and_then(|state| *state.seconds.lock().unwrap())
+        .unwrap_or(0)
+}
+
+/// 是否处于离开变暗状态
+fn is_dimmed(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    let overlay_manager = state.overlay_manager.lock().unwrap();
+    overlay_manager.as_ref().is_some_and(|manager| manager.is_idle())
+}
+
+/// 进入或退出离开状态，并按当前显示器（锁定时为锁定的显示器）更新遮罩
+fn set_dimmed(app: &AppHandle, dimmed: bool) {
+    {
+        let state = app.state::<AppState>();
+        let overlay_manager = state.overlay_manager.lock().unwrap();
+        if let Some(manager) = overlay_manager.as_ref() {
+            manager.set_idle(dimmed);
+        }
+    }
+    actions::refresh_overlays(app, &actions::load_config(app));
+}
+
+/// 有输入时从离开状态恢复
+pub fn wake(app: &AppHandle) {
+    if is_dimmed(app) {
+        set_dimmed(app, false);
+    }
+}
+
+/// 启动空闲监听线程（离开变暗或休息提醒开启时每秒采样一次）
+pub fn start_idle_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        let sampler = IdleSampler::default();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let config = actions::load_config(&app);
+            let threshold = config.idle_dim_minutes * 60;
+            let idle = if threshold > 0 || config.breaks.enabled {
+                sampler.idle_seconds()
+            } else {
+                None
+            };
+            if let Some(state) = app.try_state::<IdleState>() {
+                *state.seconds.lock().unwrap() = idle;
+            }
+
+            if let Some(dimmed) = idle_transition(is_dimmed(&app), idle.unwrap_or(0), threshold) {
+                set_dimmed(&app, dimmed);
+            }
+        }
+    });
+}
+
+/// 查询系统的空闲时间（只在空闲监听线程中使用）
+#[derive(Default)]
+struct IdleSampler {
+    /// 第一次查询时打开，之后一直复用；无法打开（如没有 X11）时不再重试
+    #[cfg(target_os = "linux")]
+    x11: std::cell::OnceCell<Option<X11Idle>>,
+}
+
+impl IdleSampler {
+    /// 空闲秒数，无法获取时返回 None
+    fn idle_seconds(&self) -> Option<u64> {
+        #[cfg(target_os = "linux")]
+        return self.x11.get_or_init(X11Idle::open).as_ref()?.idle_seconds();
+
+        #[cfg(target_os = "windows")]
+        return idle_seconds_windows();
+
+        #[cfg(target_os = "macos")]
+        return idle_seconds_macos();
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        None
+    }
+}
+
+/// X11 连接和 MIT-SCREEN-SAVER 查询结果的缓冲区
+#[cfg(target_os = "linux")]
+struct X11Idle {
+    display: *mut x11::xlib::Display,
+    info: *mut x11::xss::XScreenSaverInfo,
+    root: x11::xlib::Window,
+}
+
+#[cfg(target_os = "linux")]
+impl X11Idle {
+    fn open() -> Option<Self> {
+        use std::ptr;
+        use x11::xlib::*;
+        use x11::xss::*;
+
+        unsafe {
+            let display = XOpenDisplay(ptr::null());
+            if display.is_null() {
+                return None;
+            }
+
+            let mut event_base = 0;
+            let mut error_base = 0;
+            let info = if XScreenSaverQueryExtension(display, &mut event_base, &mut error_base) != 0 {
+                XScreenSaverAllocInfo()
+            } else {
+                ptr::null_mut()
+            };
+            if info.is_null() {
+                XCloseDisplay(display);
+                return None;
+            }
+
+            let root = XRootWindow(display, XDefaultScreen(display));
+            Some(Self { display, info, root })
+        }
+    }
+
+    fn idle_seconds(&self) -> Option<u64> {
+        unsafe {
+            if x11::xss::XScreenSaverQueryInfo(self.display, self.root, self.info) == 0 {
+                return None;
+            }
+            Some((*self.info).idle / 1000)
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for X11Idle {
+    fn drop(&mut self) {
+        unsafe {
+            x11::xlib::XFree(self.info as *mut _);
+            x11::xlib::XCloseDisplay(self.display);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn idle_seconds_windows() -> Option<u64> {
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    unsafe {
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return None;
+        }
+        // 两个值都是开机后的毫秒数（约 49 天回绕一次）
+        let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+        Some(u64::from(idle_ms) / 1000)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn idle_seconds_macos() -> Option<u64> {
+    /// kCGEventSourceStateCombinedSessionState
+    const COMBINED_SESSION_STATE: i32 = 0;
+    /// kCGAnyInputEventType
+    const ANY_INPUT_EVENT: u32 = !0;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceSecondsSinceLastEventType(state: i32, event_type: u32) -> f64;
+    }
+
+    let seconds =
+        unsafe { CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT) };
+    if seconds.is_finite() && seconds >= 0.0 {
+        Some(seconds as u64)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_transition() {
+        assert_eq!(idle_transition(false, 299, 300), None);
+        assert_eq!(idle_transition(false, 300, 300), Some(true));
+        assert_eq!(idle_transition(true, 600, 300), None);
+        // 有输入后恢复
+        assert_eq!(idle_transition(true, 0, 300), Some(false));
+        // 关闭后立即恢复
+        assert_eq!(idle_transition(false, 600, 0), None);
+        assert_eq!(idle_transition(true, 600, 0), Some(false));
+    }
+}
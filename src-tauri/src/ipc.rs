@@ -0,0 +1,374 @@
+// 本地 Unix socket 控制接口（JSON-RPC 2.0，每行一个请求/响应）
+//
+// 供 i3/sway/tmux 等脚本使用，例如：
+//   echo '{"jsonrpc":"2.0","id":1,"method":"update_opacity","params":{"opacity":0.4}}' \
+//     | socat - UNIX-CONNECT:$XDG_RUNTIME_DIR/monofocus.sock
+
+use crate::actions::Controller;
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// 命令行等待正在运行的实例响应的时长
+const CALL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const CONFIG_ERROR: i64 = -32000;
+
+/// 事件订阅者（subscribe 之后的连接）
+#[derive(Clone, Default)]
+pub struct Subscribers {
+    senders: Arc<Mutex<Vec<mpsc::Sender<String>>>>,
+}
+
+impl Subscribers {
+    /// 向所有订阅者推送通知，自动移除已断开的连接
+    pub fn publish(&self, method: &str, params: Value) {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params }).to_string();
+        self.senders
+            .lock()
+            .unwrap()
+            .retain(|sender| sender.send(message.clone()).is_ok());
+    }
+
+    fn subscribe(&self) -> mpsc::Receiver<String> {
+        let (tx, rx) = mpsc::channel();
+        self.senders.lock().unwrap().push(tx);
+        rx
+    }
+}
+
+/// socket 路径：$XDG_RUNTIME_DIR/monofocus.sock，没有时使用临时目录并带上用户 ID
+///
+/// 使用独立配置目录时文件名带上该目录的哈希（如 monofocus-1f2e….sock），与默认实例互不干扰。
+pub fn socket_path(config_dir: Option<&Path>) -> PathBuf {
+    let name = match config_dir {
+        Some(dir) => {
+            let mut hasher = DefaultHasher::new();
+            dir.hash(&mut hasher);
+            format!("monofocus-{:016x}", hasher.finish())
+        }
+        None => "monofocus".to_string(),
+    };
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join(format!("{}.sock", name)),
+        None => std::env::temp_dir().join(format!("{}-{}.sock", name, current_uid())),
+    }
+}
+
+/// 已绑定的 socket 文件，程序退出时删除
+pub struct ControlSocket(pub PathBuf);
+
+impl ControlSocket {
+    pub fn remove(&self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// 启动 socket 服务线程
+///
+/// socket 文件设为 0600 权限，且每个连接都会校验对端用户 ID，只接受当前用户的连接。
+pub fn start_ipc_server(
+    path: PathBuf,
+    controller: Arc<dyn Controller>,
+    subscribers: Subscribers,
+) -> io::Result<thread::JoinHandle<()>> {
+    let listener = bind(&path)?;
+
+    Ok(thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if peer_uid(&stream) != Some(current_uid()) {
+                continue;
+            }
+
+            let controller = controller.clone();
+            let subscribers = subscribers.clone();
+            thread::spawn(move || {
+                let _ = handle_connection(stream, controller.as_ref(), &subscribers);
+            });
+        }
+    }))
+}
+
+/// 绑定 socket，清理上次异常退出留下的文件
+fn bind(path: &Path) -> io::Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                "another instance is already listening",
+            ));
+        }
+        fs::remove_file(path)?;
+    }
+
+    // 绑定后改为 0600；修改权限之前的短暂窗口由每个连接的对端用户 ID 校验兜底
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// 处理一个连接：逐行读取请求并写回响应
+fn handle_connection(
+    stream: UnixStream,
+    controller: &dyn Controller,
+    subscribers: &Subscribers,
+) -> io::Result<()> {
+    let writer = Arc::new(Mutex::new(stream.try_clone()?));
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match parse_request(&line) {
+            Ok(request) if request.method == "subscribe" => {
+                // 先登记（之后的事件在通道中排队），写入响应后再开始转发，
+                // 保证客户端先收到订阅结果，且不会漏掉响应之后发布的事件
+                let receiver = subscribers.subscribe();
+                if let Some(id) = request.id {
+                    write_line(&writer, &success(id, json!(true)).to_string())?;
+                }
+                forward_notifications(receiver, writer.clone());
+                None
+            }
+            Ok(request) => {
+                let result = dispatch(controller, &request.method, &request.params);
+                request.id.map(|id| match result {
+                    Ok(value) => success(id, value),
+                    Err((code, message, data)) => error(id, code, &message, data),
+                })
+            }
+            Err((code, message)) => Some(error(Value::Null, code, &message, None)),
+        };
+
+        if let Some(response) = response {
+            write_line(&writer, &response.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 将订阅的通知写入连接，连接断开后线程退出
+fn forward_notifications(receiver: mpsc::Receiver<String>, writer: Arc<Mutex<UnixStream>>) {
+    thread::spawn(move || {
+        for message in receiver {
+            if write_line(&writer, &message).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn write_line(writer: &Mutex<UnixStream>, message: &str) -> io::Result<()> {
+    let mut stream = writer.lock().unwrap();
+    stream.write_all(message.as_bytes())?;
+    stream.write_all(b"\n")?;
+    stream.flush()
+}
+
+/// 向正在运行的实例发送一个请求并返回结果（命令行查询状态时使用）
+pub fn call(path: &Path, method: &str, params: Value) -> io::Result<Value> {
+    let stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(CALL_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    writeln!(writer, "{}", request)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    let mut response: Value = serde_json::from_str(&line)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    match response.get_mut("result") {
+        Some(result) => Ok(result.take()),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            response["error"]["message"].as_str().unwrap_or("request failed").to_string(),
+        )),
+    }
+}
+
+struct Request {
+    id: Option<Value>,
+    method: String,
+    params: Value,
+}
+
+fn parse_request(line: &str) -> Result<Request, (i64, String)> {
+    let value: Value =
+        serde_json::from_str(line).map_err(|e| (PARSE_ERROR, format!("Parse error: {}", e)))?;
+
+    let method = value
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| (INVALID_REQUEST, "Invalid Request".to_string()))?;
+
+    Ok(Request {
+        id: value.get("id").cloned(),
+        method: method.to_string(),
+        params: value.get("params").cloned().unwrap_or(Value::Null),
+    })
+}
+
+type RpcError = (i64, String, Option<Value>);
+
+/// 分发方法调用，方法名与 Tauri 命令保持一致
+fn dispatch(controller: &dyn Controller, method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "get_config" => Ok(json!(controller.get_config())),
+        "update_opacity" => {
+            let opacity = param(params, "opacity", Value::as_f64)?;
+            controller
+                .set_opacity(opacity as f32)
+                .map_err(config_error)?;
+            Ok(Value::Null)
+        }
+        "update_enabled" => {
+            let enabled = param(params, "enabled", Value::as_bool)?;
+            controller.set_enabled(enabled).map_err(config_error)?;
+            Ok(Value::Null)
+        }
+        "toggle" => controller
+            .toggle()
+            .map(|enabled| json!(enabled))
+            .map_err(config_error),
+        "apply_preset" => {
+            let name = param(params, "name", |value| value.as_str().map(str::to_string))?;
+            controller.apply_preset(&name).map_err(config_error)?;
+            Ok(Value::Null)
+        }
+        "get_current_monitor" => Ok(json!(controller.current_monitor())),
+        "get_status" => Ok(json!(controller.status())),
+        "get_monitor_info" => Ok(json!(controller.monitors())),
+        _ => Err((
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+            None,
+        )),
+    }
+}
+
+fn param<T>(
+    params: &Value,
+    name: &str,
+    convert: impl Fn(&Value) -> Option<T>,
+) -> Result<T, RpcError> {
+    params
+        .get(name)
+        .and_then(convert)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Invalid params: {}", name), None))
+}
+
+fn config_error(e: crate::config::ConfigError) -> RpcError {
+    (CONFIG_ERROR, e.to_string(), serde_json::to_value(&e).ok())
+}
+
+fn success(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error(id: Value, code: i64, message: &str, data: Option<Value>) -> Value {
+    let mut error = json!({ "code": code, "message": message });
+    if let Some(data) = data {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+fn current_uid() -> u32 {
+    unsafe { libc::getuid() }
+}
+
+/// 获取对端进程的用户 ID
+#[cfg(target_os = "linux")]
+fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    let mut cred = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut cred as *mut libc::ucred as *mut libc::c_void,
+            &mut len,
+        )
+    };
+
+    if result == 0 {
+        Some(cred.uid)
+    } else {
+        None
+    }
+}
+
+/// 获取对端进程的用户 ID
+#[cfg(not(target_os = "linux"))]
+fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    let mut uid: libc::uid_t = 0;
+    let mut gid: libc::gid_t = 0;
+
+    let result = unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) };
+    if result == 0 {
+        Some(uid)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::mock::MockController;
+
+    #[test]
+    fn test_socket_path() {
+        let default = socket_path(None);
+        assert_eq!(default.extension().unwrap(), "sock");
+
+        let isolated = socket_path(Some(Path::new("/tmp/monofocus-a")));
+        assert_ne!(isolated, default);
+        assert_eq!(isolated.parent(), default.parent());
+        assert_eq!(isolated, socket_path(Some(Path::new("/tmp/monofocus-a"))));
+        assert_ne!(isolated, socket_path(Some(Path::new("/tmp/monofocus-b"))));
+    }
+
+    #[test]
+    fn test_dispatch_errors() {
+        let controller = MockController::default();
+
+        let result = dispatch(&controller, "reboot", &Value::Null);
+        assert_eq!(result.unwrap_err().0, METHOD_NOT_FOUND);
+
+        let result = dispatch(&controller, "update_opacity", &json!({ "value": 0.4 }));
+        assert_eq!(result.unwrap_err().0, INVALID_PARAMS);
+
+        let result = dispatch(&controller, "apply_preset", &json!({ "name": "Gaming" }));
+        let (code, _, data) = result.unwrap_err();
+        assert_eq!(code, CONFIG_ERROR);
+        assert_eq!(data.unwrap()["code"], "unknown_preset");
+
+        assert_eq!(parse_request("{not json").err().unwrap().0, PARSE_ERROR);
+    }
+}
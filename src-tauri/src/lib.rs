@@ -0,0 +1,557 @@
+// 应用初始化和 Tauri 命令（可执行文件的入口在 main.rs）
+
+pub mod actions;
+mod autostart;
+mod breaks;
+pub mod cli;
+pub mod config;
+mod config_watcher;
+#[cfg(target_os = "linux")]
+mod dbus;
+mod focus;
+mod hotkeys;
+mod i18n;
+mod idle;
+#[cfg(unix)]
+pub mod ipc;
+pub mod monitor;
+mod mouse_watcher;
+mod overlay;
+mod pause;
+mod policy;
+mod theme;
+mod tray;
+
+use actions::{apply_config, patch_and_apply};
+use autostart::AutostartStatus;
+use breaks::BreakStatus;
+use focus::FocusStatus;
+use config::{config_schema, AppConfig, ConfigError, ConfigManager, CONFIG_DIR_ENV};
+use hotkeys::{HotkeyState, HotkeyStatus};
+use config_watcher::start_config_watcher;
+use monitor::{get_monitors, normalize_layout, start_monitor_watcher, MonitorInfo, UIRect};
+use mouse_watcher::{start_mouse_watcher, get_mouse_position, find_monitor_at_position};
+use overlay::{start_gradual_timer, OverlayManager};
+use pause::{PauseDuration, PauseState};
+use policy::LockedSettings;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tauri::{Manager, State};
+
+// 应用状态
+struct AppState {
+    config_manager: Arc<Mutex<ConfigManager>>,
+    // 当前应用的配置，加载或修改后在 apply_config 中更新，避免每次读取都访问文件
+    config: Arc<Mutex<AppConfig>>,
+    overlay_manager: Arc<Mutex<Option<OverlayManager>>>,
+    current_monitor_id: Arc<Mutex<Option<String>>>,
+    // 锁定的显示器，锁定期间不跟随鼠标切换
+    locked_monitor_id: Arc<Mutex<Option<String>>>,
+}
+
+// Tauri 命令：获取所有显示器信息
+#[tauri::command]
+fn get_monitor_info() -> Vec<MonitorInfo> {
+    get_monitors()
+}
+
+// Tauri 命令：获取 UI 布局数据
+#[tauri::command]
+fn get_monitor_layout(container_width: f32, container_height: f32) -> Vec<UIRect> {
+    let monitors = get_monitors();
+    normalize_layout(&monitors, container_width, container_height)
+}
+
+// Tauri 命令：获取当前配置
+#[tauri::command]
+fn get_config(app: tauri::AppHandle) -> AppConfig {
+    actions::load_config(&app)
+}
+
+// Tauri 命令：合并部分配置（如 {"opacity": 0.4}），返回更新后的完整配置
+#[tauri::command]
+fn patch_config(patch: serde_json::Value, app: tauri::AppHandle) -> Result<AppConfig, ConfigError> {
+    patch_and_apply(&app, patch)
+}
+
+// Tauri 命令：更新透明度
+#[tauri::command]
+fn update_opacity(opacity: f32, app: tauri::AppHandle) -> Result<(), ConfigError> {
+    actions::set_opacity(&app, opacity)
+}
+
+// Tauri 命令：更新启用状态
+#[tauri::command]
+fn update_enabled(enabled: bool, app: tauri::AppHandle) -> Result<(), ConfigError> {
+    patch_and_apply(&app, json!({ "enabled": enabled })).map(|_| ())
+}
+
+// Tauri 命令：更新自启动
+#[tauri::command]
+fn update_auto_start(auto_start: bool, app: tauri::AppHandle) -> Result<(), ConfigError> {
+    patch_and_apply(&app, json!({ "auto_start": auto_start })).map(|_| ())
+}
+
+// Tauri 命令：更新动画时长
+#[tauri::command]
+fn update_animation_duration(duration: u64, app: tauri::AppHandle) -> Result<(), ConfigError> {
+    patch_and_apply(&app, json!({ "animation_duration": duration })).map(|_| ())
+}
+
+// Tauri 命令：更新语言
+#[tauri::command]
+fn update_language(language: String, app: tauri::AppHandle) -> Result<(), ConfigError> {
+    patch_and_apply(&app, json!({ "language": language })).map(|_| ())
+}
+
+// Tauri 命令：查询自启动的实际状态
+#[tauri::command]
+fn get_auto_start_status(state: State<AppState>) -> AutostartStatus {
+    state.config_manager.lock().unwrap().auto_start_status()
+}
+
+// Tauri 命令：获取被管理员锁定的设置
+#[tauri::command]
+fn get_locked_settings(state: State<AppState>) -> LockedSettings {
+    state.config_manager.lock().unwrap().locked_settings()
+}
+
+// Tauri 命令：获取配置文件的 JSON Schema
+#[tauri::command]
+fn get_config_schema() -> serde_json::Value {
+    config_schema()
+}
+
+// Tauri 命令：应用预设
+#[tauri::command]
+fn apply_preset(name: String, app: tauri::AppHandle) -> Result<(), ConfigError> {
+    actions::apply_preset(&app, &name)
+}
+
+// Tauri 命令：获取当前鼠标所在的显示器
+#[tauri::command]
+fn get_current_monitor(state: State<AppState>) -> Option<String> {
+    state.current_monitor_id.lock().unwrap().clone()
+}
+
+// Tauri 命令：获取锁定的显示器
+#[tauri::command]
+fn get_focus_lock(state: State<AppState>) -> Option<String> {
+    state.locked_monitor_id.lock().unwrap().clone()
+}
+
+// Tauri 命令：锁定指定显示器（null 解除锁定），返回锁定的显示器
+#[tauri::command]
+fn set_focus_lock(monitor_id: Option<String>, app: tauri::AppHandle) -> Result<Option<String>, String> {
+    if let Some(id) = &monitor_id {
+        if !get_monitors().iter().any(|monitor| &monitor.id == id) {
+            let language = actions::load_config(&app).language;
+            return Err(i18n::tf(&language, "error.unknown_monitor", &[("monitor", id.clone())]));
+        }
+    }
+    Ok(actions::set_focus_lock(&app, monitor_id))
+}
+
+// Tauri 命令：锁定当前显示器，已锁定时解除锁定
+#[tauri::command]
+fn toggle_focus_lock(app: tauri::AppHandle) -> Option<String> {
+    actions::toggle_focus_lock(&app)
+}
+
+// Tauri 命令：暂停护眼模式，到期后自动恢复
+#[tauri::command]
+fn pause(duration: PauseDuration, app: tauri::AppHandle) -> Result<PauseState, ConfigError> {
+    pause::pause(&app, duration)
+}
+
+// Tauri 命令：立即恢复护眼模式
+#[tauri::command]
+fn resume(app: tauri::AppHandle) -> Result<PauseState, ConfigError> {
+    pause::resume(&app)
+}
+
+// Tauri 命令：获取暂停状态
+#[tauri::command]
+fn get_pause_state(app: tauri::AppHandle) -> PauseState {
+    PauseState::from_config(&actions::load_config(&app))
+}
+
+// Tauri 命令：获取休息提醒状态
+#[tauri::command]
+fn get_break_status(app: tauri::AppHandle) -> BreakStatus {
+    breaks::break_status(&app, &actions::load_config(&app))
+}
+
+// Tauri 命令：立即开始休息
+#[tauri::command]
+fn start_break(app: tauri::AppHandle) -> BreakStatus {
+    breaks::start_break(&app)
+}
+
+// Tauri 命令：推迟当前的休息
+#[tauri::command]
+fn snooze_break(app: tauri::AppHandle) -> BreakStatus {
+    breaks::snooze_break(&app)
+}
+
+// Tauri 命令：跳过当前的休息
+#[tauri::command]
+fn skip_break(app: tauri::AppHandle) -> BreakStatus {
+    breaks::skip_break(&app)
+}
+
+// Tauri 命令：开始专注时段
+#[tauri::command]
+fn start_focus_session(app: tauri::AppHandle) -> FocusStatus {
+    focus::start_session(&app)
+}
+
+// Tauri 命令：结束专注时段
+#[tauri::command]
+fn stop_focus_session(app: tauri::AppHandle) -> FocusStatus {
+    focus::stop_session(&app)
+}
+
+// Tauri 命令：获取专注时段状态
+#[tauri::command]
+fn get_focus_session(app: tauri::AppHandle) -> FocusStatus {
+    focus::focus_status(&app)
+}
+
+// Tauri 命令：获取全局快捷键及其注册状态
+#[tauri::command]
+fn get_hotkeys(app: tauri::AppHandle) -> Vec<HotkeyStatus> {
+    hotkeys::hotkey_statuses(&app)
+}
+
+// Tauri 命令：修改某个操作的快捷键（空字符串表示取消绑定），返回新的注册状态
+#[tauri::command]
+fn set_hotkey(
+    action: String,
+    accelerator: String,
+    app: tauri::AppHandle,
+) -> Result<Vec<HotkeyStatus>, ConfigError> {
+    patch_and_apply(&app, json!({ "hotkeys": { action: accelerator } }))?;
+    Ok(hotkeys::hotkey_statuses(&app))
+}
+
+// Tauri 命令：获取主题设置和实际生效的主题
+#[tauri::command]
+fn get_theme(app: tauri::AppHandle) -> theme::ThemeInfo {
+    theme::theme_info(&app, &actions::load_config(&app))
+}
+
+// Tauri 命令：获取界面文本目录（与托盘共用），未指定语言时使用当前配置的语言
+#[tauri::command]
+fn get_locale_catalog(
+    language: Option<String>,
+    app: tauri::AppHandle,
+) -> std::collections::HashMap<String, String> {
+    let language = language.unwrap_or_else(|| actions::load_config(&app).language);
+    i18n::bundle(&language)
+}
+
+/// 查询正在运行的实例的状态（锁定、暂停、专注时段等只保存在运行的实例中）
+///
+/// 没有运行的实例时读取配置文件和鼠标所在的显示器。
+fn query_status(config_manager: &ConfigManager, #[cfg(unix)] socket_path: &std::path::Path) -> cli::Status {
+    #[cfg(unix)]
+    if let Ok(value) = ipc::call(socket_path, "get_status", serde_json::Value::Null) {
+        if let Ok(status) = serde_json::from_value(value) {
+            return status;
+        }
+    }
+
+    let active_monitor = get_mouse_position()
+        .and_then(|pos| find_monitor_at_position(&get_monitors(), pos));
+    cli::Status::from_config(&config_manager.load(), active_monitor)
+}
+
+/// 退出前的清理
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn cleanup(app: &tauri::AppHandle) {
+    // 删除控制 socket
+    #[cfg(unix)]
+    if let Some(socket) = app.try_state::<ipc::ControlSocket>() {
+        socket.remove();
+    }
+}
+
+/// 启动应用
+pub fn run() {
+    let cli_options = match cli::parse_args(std::env::args()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("monofocus: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // 自启动时可延迟启动，等待桌面环境就绪
+    if cli_options.startup_delay > 0 {
+        std::thread::sleep(std::time::Duration::from_secs(cli_options.startup_delay));
+    }
+    let start_hidden = cli_options.hidden;
+
+    // 指定了独立配置目录时允许与默认实例同时运行（测试、多实例隔离）
+    let isolated = cli_options.config_dir.is_some() || std::env::var_os(CONFIG_DIR_ENV).is_some();
+
+    // 初始化配置管理器
+    let config_manager = Arc::new(Mutex::new(
+        ConfigManager::new(cli_options.config_dir).expect("Failed to initialize config manager"),
+    ));
+
+    // 独立实例使用由配置目录得出的控制 socket，不占用默认实例的 socket
+    #[cfg(unix)]
+    let socket_path = {
+        let config_dir = if isolated {
+            let config_path = config_manager.lock().unwrap().config_path().to_path_buf();
+            config_path
+                .parent()
+                .map(|dir| dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()))
+        } else {
+            None
+        };
+        ipc::socket_path(config_dir.as_deref())
+    };
+
+    // status 向正在运行的实例查询后输出，不启动界面
+    if let Some(cli::CliCommand::Status { json }) = cli_options.command {
+        let status = query_status(
+            &config_manager.lock().unwrap(),
+            #[cfg(unix)]
+            &socket_path,
+        );
+        println!("{}", cli::format_status(&status, json));
+        return;
+    }
+
+    // 带命令启动（没有已运行的实例）时，界面初始化完成后执行该命令
+    let startup_command = cli_options.command.clone();
+    let start_hidden = start_hidden || startup_command.is_some();
+
+    let current_monitor_id = Arc::new(Mutex::new(None));
+
+    let mut builder = tauri::Builder::default();
+    if !isolated {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            // 第二个实例带命令启动时，在当前实例中执行该命令
+            if let Ok(cli::CliOptions { command: Some(command), .. }) = cli::parse_args(argv) {
+                if let Err(e) = actions::run_cli_command(app, command) {
+                    actions::report_error(app, &e);
+                }
+                return;
+            }
+
+            // 否则显示主窗口
+            if let Some(window) = app.get_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }));
+    }
+
+    builder
+        .system_tray(tray::create_tray())
+        .on_system_tray_event(tray::handle_tray_event)
+        .setup(move |app| {
+            // 初始化遮罩管理器
+            let overlay_manager = OverlayManager::new(app.handle());
+
+            // 主窗口默认不可见，--hidden 启动时保持隐藏
+            if !start_hidden {
+                if let Some(window) = app.get_window("main") {
+                    let _ = window.show();
+                }
+            }
+
+            // 加载配置并应用（以系统中实际的自启动项为准）
+            let config = {
+                let manager = config_manager.lock().unwrap();
+                manager.reconcile_auto_start().unwrap_or_else(|_| manager.load())
+            };
+            // 解析主题（跟随系统时检测桌面设置）
+            app.manage(theme::ThemeState::default());
+            theme::update_theme(&app.handle(), &config);
+
+            overlay_manager.update_config(actions::overlay_config(&app.handle(), &config));
+
+            // 初始化托盘菜单、图标和提示
+            app.manage(tray::TrayState::default());
+            tray::refresh_tray(&app.handle(), &config);
+
+            let overlay_manager = Arc::new(Mutex::new(Some(overlay_manager)));
+            start_gradual_timer(overlay_manager.clone());
+
+            // 设置应用状态
+            app.manage(AppState {
+                config_manager: config_manager.clone(),
+                config: Arc::new(Mutex::new(config.clone())),
+                overlay_manager: overlay_manager.clone(),
+                current_monitor_id: current_monitor_id.clone(),
+                locked_monitor_id: Arc::new(Mutex::new(None)),
+            });
+
+            // 注册全局快捷键
+            app.manage(HotkeyState::default());
+            hotkeys::register_hotkeys(&app.handle(), &config.hotkeys);
+
+            // 初始化遮罩：根据当前鼠标位置设置初始状态
+            let monitors = get_monitors();
+            if let Some(mouse_pos) = get_mouse_position() {
+                if let Some(initial_monitor_id) = find_monitor_at_position(&monitors, mouse_pos) {
+                    // 设置初始显示器 ID
+                    {
+                        let mut current = current_monitor_id.lock().unwrap();
+                        *current = Some(initial_monitor_id.clone());
+                    }
+                    // 初始化遮罩状态
+                    if let Some(manager) = overlay_manager.lock().unwrap().as_ref() {
+                        manager.update_overlays(&monitors, &initial_monitor_id);
+                    }
+                }
+            }
+
+            if let Some(command) = startup_command {
+                if let Err(e) = actions::run_cli_command(&app.handle(), command) {
+                    actions::report_error(&app.handle(), &e);
+                }
+            }
+
+            // 监听配置文件的外部修改
+            let config_path = config_manager.lock().unwrap().config_path().to_path_buf();
+            let watcher_handle = app.handle();
+
+            start_config_watcher(config_path, move |content| {
+                let state = watcher_handle.state::<AppState>();
+                let result = {
+                    let manager = state.config_manager.lock().unwrap();
+                    // 程序自己保存的修改在保存时已经应用过
+                    if manager.take_own_write(content) {
+                        return;
+                    }
+                    manager.try_load()
+                };
+
+                match result {
+                    Ok(config) => apply_config(&watcher_handle, &config),
+                    Err(e) => {
+                        // 无效的修改只上报，不覆盖用户的文件
+                        let _ = watcher_handle.emit_all("config-error", e);
+                    }
+                }
+            });
+
+            // 本地控制接口（Unix socket）
+            #[cfg(unix)]
+            {
+                let subscribers = ipc::Subscribers::default();
+                app.manage(subscribers.clone());
+                match ipc::start_ipc_server(socket_path.clone(), Arc::new(app.handle()), subscribers) {
+                    Ok(_) => {
+                        app.manage(ipc::ControlSocket(socket_path));
+                    }
+                    Err(e) => eprintln!("Failed to start control socket: {}", e),
+                }
+            }
+
+            // D-Bus 会话总线接口（总线名称全局唯一，独立实例不注册）
+            #[cfg(target_os = "linux")]
+            if !isolated {
+                match dbus::DbusService::start(Arc::new(app.handle())) {
+                    Ok(service) => {
+                        app.manage(service);
+                    }
+                    Err(e) => eprintln!("Failed to register D-Bus service: {}", e),
+                }
+            }
+
+            // 跟随系统主题时检测浅色/深色切换
+            theme::start_theme_watcher(app.handle());
+
+            // 暂停到期后自动恢复
+            pause::start_pause_timer(app.handle());
+
+            // 休息提醒
+            app.manage(breaks::BreakState::default());
+            breaks::start_break_timer(app.handle());
+
+            // 专注时段
+            app.manage(focus::FocusState::default());
+            focus::start_focus_timer(app.handle());
+
+            // 离开后所有显示器变暗（同时为休息提醒提供空闲时间）
+            app.manage(idle::IdleState::default());
+            idle::start_idle_watcher(app.handle());
+
+            // 显示器接入或移除时重建遮罩和托盘的显示器子菜单
+            let monitor_handle = app.handle();
+            start_monitor_watcher(move |monitors| {
+                actions::monitors_changed(&monitor_handle, monitors);
+            });
+
+            // 启动鼠标监听
+            let app_handle = app.handle();
+            let move_handle = app.handle();
+            start_mouse_watcher(
+                move |monitor_id| {
+                    // 更新当前显示器和遮罩层，并通知前端和外部订阅者
+                    actions::focus_monitor(&app_handle, monitor_id);
+                },
+                move || {
+                    // 离开后第一次移动鼠标时立即恢复
+                    idle::wake(&move_handle);
+                },
+            );
+
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_monitor_info,
+            get_monitor_layout,
+            get_config,
+            patch_config,
+            update_opacity,
+            update_enabled,
+            update_auto_start,
+            update_animation_duration,
+            update_language,
+            apply_preset,
+            get_current_monitor,
+            get_config_schema,
+            get_locked_settings,
+            get_auto_start_status,
+            get_hotkeys,
+            get_locale_catalog,
+            get_theme,
+            get_focus_lock,
+            set_focus_lock,
+            toggle_focus_lock,
+            pause,
+            resume,
+            get_pause_state,
+            get_break_status,
+            start_break,
+            snooze_break,
+            skip_break,
+            start_focus_session,
+            stop_focus_session,
+            get_focus_session,
+            set_hotkey,
+        ])
+        .on_window_event(|event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event.event() {
+                // 阻止关闭，改为隐藏
+                event.window().hide().unwrap();
+                api.prevent_close();
+            }
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                cleanup(app);
+            }
+        });
+}
+
@@ -1,8 +1,16 @@
 use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 检查显示器连接和布局变化的间隔
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MonitorInfo {
     pub id: String,
+    /// 系统中的显示器名称（如 "HDMI-1"、"\\.\DISPLAY1"），用于托盘菜单和排除列表
+    #[serde(default)]
+    pub name: String,
     pub x: i32,
     pub y: i32,
     pub width: i32,
@@ -35,6 +43,27 @@ pub fn get_monitors() -> Vec<MonitorInfo> {
     return get_monitors_linux();
 }
 
+/// 启动显示器监听线程：接入、移除显示器或布局变化时回调
+pub fn start_monitor_watcher<F>(callback: F) -> thread::JoinHandle<()>
+where
+    F: Fn(Vec<MonitorInfo>) + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut last_monitors = get_monitors();
+
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+
+            let monitors = get_monitors();
+            // 获取失败（如 X 连接暂时不可用）时不视为显示器全部移除
+            if !monitors.is_empty() && monitors != last_monitors {
+                callback(monitors.clone());
+                last_monitors = monitors;
+            }
+        }
+    })
+}
+
 /// 将显示器信息标准化为 UI 布局
 pub fn normalize_layout(monitors: &[MonitorInfo], container_width: f32, container_height: f32) -> Vec<UIRect> {
     if monitors.is_empty() {
@@ -97,9 +126,12 @@ fn get_monitors_windows() -> Vec<MonitorInfo> {
             
             // 生成唯一 ID
             let id = format!("monitor_{}", monitors.len());
+            let name_len = info.szDevice.iter().position(|&c| c == 0).unwrap_or(info.szDevice.len());
+            let name = String::from_utf16_lossy(&info.szDevice[..name_len]);
             
             monitors.push(MonitorInfo {
                 id,
+                name,
                 x: rect.left,
                 y: rect.top,
                 width,
@@ -147,6 +179,7 @@ fn get_monitors_macos() -> Vec<MonitorInfo> {
                 
                 monitors.push(MonitorInfo {
                     id: format!("monitor_{}", idx),
+                    name: format!("Display {}", idx + 1),
                     x: bounds.origin.x as i32,
                     y: bounds.origin.y as i32,
                     width: bounds.size.width as i32,
@@ -194,8 +227,13 @@ fn get_monitors_linux() -> Vec<MonitorInfo> {
                 let crtc_info = XRRGetCrtcInfo(display, resources, (*output_info).crtc);
                 
                 if !crtc_info.is_null() {
+                    let name = std::ffi::CStr::from_ptr((*output_info).name)
+                        .to_string_lossy()
+                        .into_owned();
+
                     monitors.push(MonitorInfo {
                         id: format!("monitor_{}", i),
+                        name,
                         x: (*crtc_info).x,
                         y: (*crtc_info).y,
                         width: (*crtc_info).width as i32,
@@ -228,6 +266,7 @@ mod tests {
         let monitors = vec![
             MonitorInfo {
                 id: "1".to_string(),
+                name: "DP-1".to_string(),
                 x: 0,
                 y: 0,
                 width: 1920,
@@ -238,6 +277,7 @@ mod tests {
             },
             MonitorInfo {
                 id: "2".to_string(),
+                name: "HDMI-1".to_string(),
                 x: 1920,
                 y: 0,
                 width: 1920,
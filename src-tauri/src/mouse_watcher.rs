@@ -36,15 +36,24 @@ pub fn find_monitor_at_position(monitors: &[MonitorInfo], pos: MousePosition) ->
 }
 
 /// 启动鼠标监听线程
-pub fn start_mouse_watcher<F>(callback: F) -> thread::JoinHandle<()>
+///
+/// 鼠标移动时先调用 `on_move`（用于从离开状态恢复），显示器变化时再调用 `callback`。
+pub fn start_mouse_watcher<F, M>(callback: F, on_move: M) -> thread::JoinHandle<()>
 where
     F: Fn(String) + Send + 'static,
+    M: Fn() + Send + 'static,
 {
     thread::spawn(move || {
         let mut last_monitor_id: Option<String> = None;
+        let mut last_position: Option<(i32, i32)> = None;
         
         loop {
             if let Some(pos) = get_mouse_position() {
+                if last_position.is_some_and(|last| last != (pos.x, pos.y)) {
+                    on_move();
+                }
+                last_position = Some((pos.x, pos.y));
+
                 let monitors = crate::monitor::get_monitors();
                 
                 if let Some(current_monitor_id) = find_monitor_at_position(&monitors, pos) {
@@ -158,6 +167,7 @@ mod tests {
         let monitors = vec![
             MonitorInfo {
                 id: "1".to_string(),
+                name: "DP-1".to_string(),
                 x: 0,
                 y: 0,
                 width: 1920,
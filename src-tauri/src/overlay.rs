@@ -1,14 +1,31 @@
+use crate::config::{DimCurve, GradualDimConfig, RecentMonitorsConfig, MAX_RECENT_MONITORS};
 use crate::monitor::MonitorInfo;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Window, WindowBuilder};
 
+/// 逐渐变暗时更新遮罩的间隔
+const GRADUAL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 逐渐变暗时透明度变化小于这个值不更新窗口
+const GRADUAL_STEP: f32 = 0.01;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OverlayConfig {
     pub opacity: f32, // 0.0 - 1.0 (0% - 100%)
     pub enabled: bool,
     pub animation_duration: u64, // 动画时长（毫秒），0 表示无动画
+    #[serde(default)]
+    pub excluded_monitors: Vec<String>, // 不显示遮罩的显示器名称
+    #[serde(default)]
+    pub color: [u8; 3], // 遮罩颜色（RGB）
+    #[serde(default)]
+    pub gradual: Option<GradualDimConfig>, // 逐渐变暗（None 时使用固定的 opacity）
+    #[serde(default)]
+    pub recent: Option<RecentMonitorsConfig>, // 最近使用的显示器不变暗（None 时只有当前显示器）
 }
 
 impl Default for OverlayConfig {
@@ -17,14 +34,54 @@ impl Default for OverlayConfig {
             opacity: 0.6,
             enabled: true,
             animation_duration: 300,
+            excluded_monitors: Vec::new(),
+            color: [0, 0, 0],
+            gradual: None,
+            recent: None,
         }
     }
 }
 
+/// 把显示器移到最近使用列表的最前面
+fn visit_monitor(recent: &mut Vec<String>, monitor_id: &str) {
+    recent.retain(|id| id != monitor_id);
+    recent.insert(0, monitor_id.to_string());
+    recent.truncate(MAX_RECENT_MONITORS as usize);
+}
+
+/// 最近使用列表中第 `rank` 位（0 为当前显示器）的透明度，不超过 `cap`
+pub fn recent_opacity(config: &RecentMonitorsConfig, rank: usize, cap: f32) -> f32 {
+    (config.rank_opacity * rank as f32).min(cap)
+}
+
+/// 鼠标离开 `elapsed` 后的透明度
+pub fn gradual_opacity(config: &GradualDimConfig, elapsed: Duration) -> f32 {
+    let ramp = (config.ramp_minutes * 60) as f32;
+    let t = (elapsed.as_secs_f32() / ramp).clamp(0.0, 1.0);
+    let eased = match config.curve {
+        DimCurve::Linear => t,
+        DimCurve::EaseIn => t * t,
+        DimCurve::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+    };
+    config.start_opacity + (config.max_opacity - config.start_opacity) * eased
+}
+
 pub struct OverlayManager {
     app: AppHandle,
     overlays: Arc<Mutex<HashMap<String, Window>>>,
     config: Arc<Mutex<OverlayConfig>>,
+    // 休息期间所有显示器都显示休息遮罩，不跟随鼠标切换
+    on_break: Arc<Mutex<bool>>,
+    // 离开后变暗：当前显示器也显示遮罩，有输入后恢复
+    idle: Arc<Mutex<bool>>,
+    // 逐渐变暗：上次的活跃显示器、每个显示器失去焦点的时间和正在显示的遮罩透明度
+    active_monitor: Arc<Mutex<Option<String>>>,
+    left_at: Arc<Mutex<HashMap<String, Instant>>>,
+    shown: Arc<Mutex<HashMap<String, f32>>>,
+    // 鼠标访问过的显示器，最近的在前
+    recent: Arc<Mutex<Vec<String>>>,
+    // 逐渐变暗是否生效，变化时唤醒逐渐变暗的更新线程
+    gradual_enabled: Arc<(Mutex<bool>, Condvar)>,
 }
 
 impl OverlayManager {
@@ -33,6 +90,13 @@ impl OverlayManager {
             app,
             overlays: Arc::new(Mutex::new(HashMap::new())),
             config: Arc::new(Mutex::new(OverlayConfig::default())),
+            on_break: Arc::new(Mutex::new(false)),
+            idle: Arc::new(Mutex::new(false)),
+            active_monitor: Arc::new(Mutex::new(None)),
+            left_at: Arc::new(Mutex::new(HashMap::new())),
+            shown: Arc::new(Mutex::new(HashMap::new())),
+            recent: Arc::new(Mutex::new(Vec::new())),
+            gradual_enabled: Arc::new((Mutex::new(false), Condvar::new())),
         }
     }
 
@@ -42,18 +106,24 @@ impl OverlayManager {
         *current_config = config.clone();
         drop(current_config);
 
+        let (enabled, changed) = &*self.gradual_enabled;
+        *enabled.lock().unwrap() = config.enabled && config.gradual.is_some();
+        changed.notify_all();
+
         // 更新所有现有遮罩
         self.update_all_overlays();
     }
 
-    /// 获取当前配置
-    pub fn get_config(&self) -> OverlayConfig {
-        self.config.lock().unwrap().clone()
-    }
-
     /// 更新遮罩显示（根据当前活跃的显示器）
     pub fn update_overlays(&self, monitors: &[MonitorInfo], active_monitor_id: &str) {
+        self.track_active_monitor(active_monitor_id);
+
+        if *self.on_break.lock().unwrap() {
+            return;
+        }
+
         let config = self.config.lock().unwrap().clone();
+        let idle = *self.idle.lock().unwrap();
 
         if !config.enabled {
             self.hide_all_overlays();
@@ -72,18 +142,159 @@ impl OverlayManager {
 
             // 根据是否是活跃显示器来显示/隐藏
             if let Some(window) = overlays.get(&monitor.id) {
-                if monitor.id == active_monitor_id {
-                    // 隐藏活跃显示器的遮罩
-                    self.emit_overlay_event(window, "overlay-hide");
+                let active = !idle
+                    && (monitor.id == active_monitor_id
+                        || self.recent_rank_opacity(&config, &monitor.id) == Some(0.0));
+                if active || config.excluded_monitors.contains(&monitor.name) {
+                    // 隐藏活跃显示器和最近使用的显示器（离开时除外）以及被排除的显示器的遮罩
+                    self.emit_overlay_event(&monitor.id, window, "overlay-hide");
                 } else {
                     // 显示非活跃显示器的遮罩
                     self.send_overlay_config(window, &config);
-                    self.emit_overlay_event(window, "overlay-show");
+                    self.emit_overlay_event(&monitor.id, window, "overlay-show");
                 }
             }
         }
     }
 
+    /// 记录活跃显示器的变化：刚失去焦点的显示器从此刻开始逐渐变暗
+    fn track_active_monitor(&self, active_monitor_id: &str) {
+        let mut active_monitor = self.active_monitor.lock().unwrap();
+        if active_monitor.as_deref() == Some(active_monitor_id) {
+            return;
+        }
+
+        let mut left_at = self.left_at.lock().unwrap();
+        if let Some(previous) = active_monitor.take() {
+            left_at.insert(previous, Instant::now());
+        }
+        left_at.remove(active_monitor_id);
+        *active_monitor = Some(active_monitor_id.to_string());
+        visit_monitor(&mut self.recent.lock().unwrap(), active_monitor_id);
+    }
+
+    /// 最近使用的显示器的透明度，不在最近的 count 台之内时返回 None
+    fn recent_rank_opacity(&self, config: &OverlayConfig, monitor_id: &str) -> Option<f32> {
+        let recent_config = config.recent.as_ref()?;
+        let rank = self.recent.lock().unwrap().iter().position(|id| id == monitor_id)?;
+        if rank as u64 >= recent_config.count {
+            return None;
+        }
+        // 不比没有访问过的显示器更暗
+        let cap = config.gradual.as_ref().map_or(config.opacity, |gradual| gradual.start_opacity);
+        Some(recent_opacity(recent_config, rank, cap))
+    }
+
+    /// 显示器的遮罩透明度：最近使用的显示器按先后顺序计算；
+    /// 逐渐变暗时按失去焦点的时长计算（没有记录的显示器使用上限）
+    fn monitor_opacity(&self, config: &OverlayConfig, monitor_id: &str, now: Instant) -> f32 {
+        if !self.is_idle() {
+            if let Some(opacity) = self.recent_rank_opacity(config, monitor_id) {
+                return opacity;
+            }
+        }
+        let Some(gradual) = &config.gradual else {
+            return config.opacity;
+        };
+        match self.left_at.lock().unwrap().get(monitor_id) {
+            Some(left) => gradual_opacity(gradual, now.duration_since(*left)),
+            None => gradual.max_opacity,
+        }
+    }
+
+    /// 逐渐变暗：只更新正在显示、且透明度变化超过 GRADUAL_STEP 的遮罩
+    pub fn update_gradual(&self) {
+        if *self.on_break.lock().unwrap() {
+            return;
+        }
+        let config = self.config.lock().unwrap().clone();
+        let Some(gradual) = config.gradual.as_ref().filter(|_| config.enabled) else {
+            return;
+        };
+
+        let now = Instant::now();
+        let overlays = self.overlays.lock().unwrap();
+        let left_at = self.left_at.lock().unwrap();
+        let mut shown = self.shown.lock().unwrap();
+
+        let idle = self.is_idle();
+
+        for (monitor_id, left) in left_at.iter() {
+            let Some(&current) = shown.get(monitor_id) else {
+                continue;
+            };
+            // 最近使用的显示器保持按顺序计算的透明度
+            if !idle && self.recent_rank_opacity(&config, monitor_id).is_some() {
+                continue;
+            }
+            let opacity = gradual_opacity(gradual, now.duration_since(*left));
+            if (opacity - current).abs() < GRADUAL_STEP {
+                continue;
+            }
+            if let Some(window) = overlays.get(monitor_id) {
+                self.set_background(window, config.color, opacity);
+                shown.insert(monitor_id.clone(), opacity);
+            }
+        }
+    }
+
+    /// 设置是否处于离开状态（之后由调用方按当前显示器更新遮罩）
+    pub fn set_idle(&self, idle: bool) {
+        *self.idle.lock().unwrap() = idle;
+    }
+
+    pub fn is_idle(&self) -> bool {
+        *self.idle.lock().unwrap()
+    }
+
+    /// 显示休息遮罩：所有显示器（包括当前显示器和被排除的显示器）显示遮罩和倒计时
+    pub fn show_break(&self, monitors: &[MonitorInfo], opacity: f32, remaining_secs: u64, title: &str, hint: &str) {
+        *self.on_break.lock().unwrap() = true;
+
+        let config = self.config.lock().unwrap().clone();
+        let [r, g, b] = config.color;
+        // 文本按 JSON 字符串传入，避免引号等字符破坏脚本
+        let script = format!(
+            "window.showBreak('rgba({}, {}, {}, {})', {}, {}, {});",
+            r,
+            g,
+            b,
+            opacity,
+            remaining_secs,
+            serde_json::Value::from(title),
+            serde_json::Value::from(hint),
+        );
+
+        let mut overlays = self.overlays.lock().unwrap();
+        for monitor in monitors {
+            if !overlays.contains_key(&monitor.id) {
+                if let Ok(window) = self.create_overlay(monitor, &config) {
+                    overlays.insert(monitor.id.clone(), window);
+                }
+            }
+            if let Some(window) = overlays.get(&monitor.id) {
+                self.send_overlay_config(window, &config);
+                let _ = window.eval(&script);
+            }
+        }
+    }
+
+    /// 更新休息倒计时
+    pub fn update_break(&self, remaining_secs: u64) {
+        let script = format!("window.updateBreak({});", remaining_secs);
+        for window in self.overlays.lock().unwrap().values() {
+            let _ = window.eval(&script);
+        }
+    }
+
+    /// 结束休息（之后由调用方按配置恢复遮罩）
+    pub fn hide_break(&self) {
+        *self.on_break.lock().unwrap() = false;
+        for window in self.overlays.lock().unwrap().values() {
+            let _ = window.eval("window.hideBreak();");
+        }
+    }
+
     /// 创建遮罩窗口
     fn create_overlay(&self, monitor: &MonitorInfo, _config: &OverlayConfig) -> Result<Window, tauri::Error> {
         let label = format!("overlay_{}", monitor.id);
@@ -132,55 +343,73 @@ impl OverlayManager {
     }
 
     /// 发送事件到遮罩窗口（直接设置样式）
-    fn emit_overlay_event(&self, window: &Window, event: &str) {
-        let script = match event {
+    fn emit_overlay_event(&self, monitor_id: &str, window: &Window, event: &str) {
+        match event {
             "overlay-show" => {
-                let opacity = self.config.lock().unwrap().opacity;
-                format!("document.body.style.backgroundColor = 'rgba(0, 0, 0, {})';", opacity)
+                let config = self.config.lock().unwrap().clone();
+                let opacity = self.monitor_opacity(&config, monitor_id, Instant::now());
+                self.set_background(window, config.color, opacity);
+                self.shown.lock().unwrap().insert(monitor_id.to_string(), opacity);
             },
             "overlay-hide" => {
-                "document.body.style.backgroundColor = 'transparent';".to_string()
+                let _ = window.eval("document.body.style.backgroundColor = 'transparent';");
+                self.shown.lock().unwrap().remove(monitor_id);
             },
-            _ => return,
-        };
-        let _ = window.eval(&script);
+            _ => {}
+        }
+    }
+
+    fn set_background(&self, window: &Window, [r, g, b]: [u8; 3], opacity: f32) {
+        let _ = window.eval(&format!(
+            "document.body.style.backgroundColor = 'rgba({}, {}, {}, {})';",
+            r, g, b, opacity
+        ));
     }
 
     /// 隐藏所有遮罩
     pub fn hide_all_overlays(&self) {
         let overlays = self.overlays.lock().unwrap();
-        for window in overlays.values() {
+        for (monitor_id, window) in overlays.iter() {
             // 发送隐藏事件
-            self.emit_overlay_event(window, "overlay-hide");
+            self.emit_overlay_event(monitor_id, window, "overlay-hide");
         }
     }
 
     /// 更新所有遮罩（透明度等）
     fn update_all_overlays(&self) {
+        if *self.on_break.lock().unwrap() {
+            return;
+        }
+
         let config = self.config.lock().unwrap().clone();
         let overlays = self.overlays.lock().unwrap();
         
-        for window in overlays.values() {
+        for (monitor_id, window) in overlays.iter() {
             // 发送配置更新
             self.send_overlay_config(window, &config);
             
             if config.enabled {
                 // 发送显示事件
-                self.emit_overlay_event(window, "overlay-show");
+                self.emit_overlay_event(monitor_id, window, "overlay-show");
             } else {
                 // 发送隐藏事件
-                self.emit_overlay_event(window, "overlay-hide");
+                self.emit_overlay_event(monitor_id, window, "overlay-hide");
             }
         }
     }
 
-    /// 清理所有遮罩（程序退出时使用）
-    #[allow(dead_code)]
+    /// 清理所有遮罩（显示器变化后由 update_overlays 按新的布局重新创建）
     pub fn cleanup(&self) {
         let mut overlays = self.overlays.lock().unwrap();
         for (_, window) in overlays.drain() {
             let _ = window.close();
         }
+
+        // 显示器 ID 按顺序分配，布局变化后重新记录
+        self.shown.lock().unwrap().clear();
+        self.left_at.lock().unwrap().clear();
+        self.recent.lock().unwrap().clear();
+        *self.active_monitor.lock().unwrap() = None;
     }
 
     #[cfg(target_os = "windows")]
@@ -255,3 +484,81 @@ impl OverlayManager {
     }
 }
 
+/// 启动逐渐变暗的更新线程
+pub fn start_gradual_timer(overlay_manager: Arc<Mutex<Option<OverlayManager>>>) {
+    let Some(gradual_enabled) = overlay_manager
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.gradual_enabled.clone())
+    else {
+        return;
+    };
+
+    thread::spawn(move || loop {
+        // 未开启逐渐变暗时一直等待，不占用遮罩管理器的锁
+        {
+            let (enabled, changed) = &*gradual_enabled;
+            let _enabled = changed
+                .wait_while(enabled.lock().unwrap(), |enabled| !*enabled)
+                .unwrap();
+        }
+        thread::sleep(GRADUAL_INTERVAL);
+
+        if let Some(manager) = overlay_manager.lock().unwrap().as_ref() {
+            manager.update_gradual();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradual_opacity() {
+        let mut config = GradualDimConfig {
+            enabled: true,
+            start_opacity: 0.2,
+            max_opacity: 0.8,
+            ramp_minutes: 10,
+            curve: DimCurve::Linear,
+        };
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+
+        assert_eq!(gradual_opacity(&config, Duration::ZERO), 0.2);
+        assert!((gradual_opacity(&config, minutes(5)) - 0.5).abs() < 1e-6);
+        assert_eq!(gradual_opacity(&config, minutes(10)), 0.8);
+        // 到达上限后不再加深
+        assert_eq!(gradual_opacity(&config, minutes(60)), 0.8);
+
+        config.curve = DimCurve::EaseIn;
+        assert!((gradual_opacity(&config, minutes(5)) - 0.35).abs() < 1e-6);
+
+        config.curve = DimCurve::EaseOut;
+        assert!((gradual_opacity(&config, minutes(5)) - 0.65).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_recent_monitors() {
+        let mut recent = Vec::new();
+        for id in ["monitor_1", "monitor_2", "monitor_3", "monitor_1"] {
+            visit_monitor(&mut recent, id);
+        }
+        assert_eq!(recent, ["monitor_1", "monitor_3", "monitor_2"]);
+
+        for i in 0..MAX_RECENT_MONITORS + 2 {
+            visit_monitor(&mut recent, &format!("monitor_{}", i));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_MONITORS as usize);
+
+        let config = RecentMonitorsConfig {
+            count: 3,
+            rank_opacity: 0.25,
+        };
+        assert_eq!(recent_opacity(&config, 0, 0.6), 0.0);
+        assert_eq!(recent_opacity(&config, 1, 0.6), 0.25);
+        // 不超过普通遮罩的透明度
+        assert_eq!(recent_opacity(&config, 3, 0.6), 0.6);
+    }
+}
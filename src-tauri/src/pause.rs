@@ -0,0 +1,145 @@
+// 暂停护眼模式一段时间，到期后自动恢复
+//
+// 暂停截止时间保存在配置文件（paused_until）中，重新加载配置或重启程序后依然有效。
+
+use crate::actions;
+use crate::config::{AppConfig, ConfigError};
+use crate::tray;
+use chrono::{Duration as ChronoDuration, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// 检查暂停是否到期的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 暂停时长
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseDuration {
+    /// 暂停指定分钟数，例如 {"minutes": 15}
+    Minutes(u64),
+    /// 暂停到明天 0 点
+    UntilTomorrow,
+}
+
+/// 暂停状态（pause-changed 事件的内容）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PauseState {
+    /// 恢复时间（Unix 时间戳，秒）
+    pub paused_until: Option<u64>,
+    /// 剩余秒数
+    pub remaining_secs: Option<u64>,
+}
+
+impl PauseState {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            paused_until: config.paused_until,
+            remaining_secs: config
+                .paused_until
+                .map(|until| until.saturating_sub(now_secs())),
+        }
+    }
+}
+
+/// 当前时间（Unix 时间戳，秒）
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// 计算暂停的截止时间
+pub fn resume_time(duration: PauseDuration, now: u64) -> u64 {
+    match duration {
+        PauseDuration::Minutes(minutes) => now + minutes * 60,
+        PauseDuration::UntilTomorrow => {
+            let tomorrow = Local::now().date_naive() + ChronoDuration::days(1);
+            tomorrow
+                .and_hms_opt(0, 0, 0)
+                .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+                .map(|midnight| midnight.timestamp() as u64)
+                .unwrap_or(now + 24 * 60 * 60)
+        }
+    }
+}
+
+/// 暂停护眼模式
+pub fn pause(app: &AppHandle, duration: PauseDuration) -> Result<PauseState, ConfigError> {
+    let until = resume_time(duration, now_secs());
+    let config = actions::patch_and_apply(app, json!({ "enabled": false, "paused_until": until }))?;
+    Ok(PauseState::from_config(&config))
+}
+
+/// 立即恢复护眼模式
+pub fn resume(app: &AppHandle) -> Result<PauseState, ConfigError> {
+    let config = actions::patch_and_apply(app, json!({ "enabled": true }))?;
+    Ok(PauseState::from_config(&config))
+}
+
+/// 通知前端暂停状态变化
+pub fn notify_pause_changed(app: &AppHandle, config: &AppConfig) {
+    let _ = app.emit_all("pause-changed", PauseState::from_config(config));
+}
+
+/// 启动暂停计时线程：到期后恢复，暂停期间刷新托盘上的剩余时间
+pub fn start_pause_timer(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let config = actions::load_config(&app);
+        let Some(until) = config.paused_until else {
+            continue;
+        };
+
+        if now_secs() >= until {
+            if let Err(e) = resume(&app) {
+                eprintln!("Failed to resume after pause: {}", e);
+            }
+        } else {
+            // 剩余分钟数变化时才会重建菜单
+            tray::refresh_tray(&app, &config);
+        }
+    });
+}
+
+/// 剩余分钟数（向上取整）
+pub fn remaining_minutes(remaining_secs: u64) -> u64 {
+    remaining_secs.div_ceil(60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_time() {
+        assert_eq!(resume_time(PauseDuration::Minutes(15), 1000), 1000 + 900);
+
+        let now = now_secs();
+        let tomorrow = resume_time(PauseDuration::UntilTomorrow, now);
+        assert!(tomorrow > now);
+        assert!(tomorrow <= now + 25 * 60 * 60);
+    }
+
+    #[test]
+    fn test_parse_pause_duration() {
+        let duration: PauseDuration = serde_json::from_value(json!({ "minutes": 60 })).unwrap();
+        assert_eq!(duration, PauseDuration::Minutes(60));
+
+        let duration: PauseDuration = serde_json::from_value(json!("until_tomorrow")).unwrap();
+        assert_eq!(duration, PauseDuration::UntilTomorrow);
+    }
+
+    #[test]
+    fn test_remaining_minutes() {
+        assert_eq!(remaining_minutes(0), 0);
+        assert_eq!(remaining_minutes(1), 1);
+        assert_eq!(remaining_minutes(60), 1);
+        assert_eq!(remaining_minutes(61), 2);
+    }
+}
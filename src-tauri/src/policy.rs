@@ -0,0 +1,351 @@
+use crate::config::{merge_layer, ConfigError};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 系统级默认配置文件名（合并在用户 config.json 之下）
+const DEFAULTS_FILE: &str = "defaults.json";
+
+/// 管理员策略文件名
+const POLICY_FILE: &str = "policy.json";
+
+/// 受透明度下限约束的字段（JSON Pointer）
+const OPACITY_FIELDS: &[&str] = &[
+    "/opacity",
+    "/gradual_dim/start_opacity",
+    "/gradual_dim/max_opacity",
+    "/recent_monitors/rank_opacity",
+    "/focus/opacity",
+    "/breaks/opacity",
+];
+
+/// 设置了透明度下限时不允许排除显示器（被排除的显示器不显示遮罩）
+const EXCLUDED_MONITORS: &str = "excluded_monitors";
+
+/// 管理员策略（policy.json）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Policy {
+    /// 被锁定的字段及其强制值，例如 {"enabled": true}；嵌套对象只锁定其中列出的字段，
+    /// 例如 {"breaks": {"enabled": true}}
+    #[serde(default)]
+    pub locked: Map<String, Value>,
+    /// 透明度下限
+    #[serde(default)]
+    pub min_opacity: Option<f32>,
+}
+
+/// 提供给前端的锁定信息
+#[derive(Debug, Clone, Serialize)]
+pub struct LockedSettings {
+    pub fields: Vec<String>,
+    pub min_opacity: Option<f32>,
+}
+
+/// 系统级配置层：默认值 + 策略
+#[derive(Debug, Clone, Default)]
+pub struct SystemLayers {
+    pub defaults: Map<String, Value>,
+    pub policy: Policy,
+}
+
+/// 获取系统级配置目录（优先级从高到低）
+pub fn system_config_dirs() -> Vec<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var("PROGRAMDATA")
+            .map(|dir| vec![PathBuf::from(dir).join("MonoFocus")])
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        vec![PathBuf::from("/Library/Application Support/MonoFocus")]
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let dirs = std::env::var("XDG_CONFIG_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/etc/xdg".to_string());
+
+        dirs.split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| PathBuf::from(dir).join("MonoFocus"))
+            .collect()
+    }
+}
+
+impl SystemLayers {
+    /// 从系统目录加载默认值和策略，高优先级目录覆盖低优先级目录（嵌套对象逐层合并）
+    ///
+    /// 文件缺失或格式错误时直接忽略，不影响用户配置的加载。
+    pub fn load(dirs: &[PathBuf]) -> Self {
+        let mut layers = SystemLayers::default();
+
+        for dir in dirs.iter().rev() {
+            if let Some(defaults) = read_object(&dir.join(DEFAULTS_FILE)) {
+                deep_merge(&mut layers.defaults, defaults);
+            }
+
+            if let Some(policy) = read_object(&dir.join(POLICY_FILE))
+                .and_then(|object| serde_json::from_value::<Policy>(Value::Object(object)).ok())
+            {
+                deep_merge(&mut layers.policy.locked, policy.locked);
+                if policy.min_opacity.is_some() {
+                    layers.policy.min_opacity = policy.min_opacity;
+                }
+            }
+        }
+
+        layers
+    }
+
+    /// 将锁定值和透明度下限应用到合并后的配置
+    pub fn enforce(&self, value: &mut Value) {
+        merge_layer(value, &self.policy.locked);
+
+        if let Some(min) = self.policy.min_opacity {
+            for pointer in OPACITY_FIELDS {
+                if let Some(slot) = value.pointer_mut(pointer) {
+                    if slot.as_f64().is_some_and(|opacity| opacity < min as f64) {
+                        *slot = Value::from(min);
+                    }
+                }
+            }
+            if let Some(excluded) = value.get_mut(EXCLUDED_MONITORS) {
+                *excluded = json!([]);
+            }
+        }
+    }
+
+    /// 检查补丁是否修改了被锁定的字段或低于透明度下限
+    pub fn check_patch(&self, fields: &Map<String, Value>) -> Result<(), ConfigError> {
+        check_locked(&self.policy.locked, fields, "")?;
+
+        let Some(min) = self.policy.min_opacity else {
+            return Ok(());
+        };
+
+        let patch = Value::Object(fields.clone());
+        for pointer in OPACITY_FIELDS {
+            if let Some(opacity) = patch.pointer(pointer).and_then(Value::as_f64) {
+                if opacity < min as f64 {
+                    return Err(ConfigError::OpacityBelowMinimum {
+                        opacity: opacity as f32,
+                        min,
+                    });
+                }
+            }
+        }
+
+        let excluded = fields.get(EXCLUDED_MONITORS).and_then(Value::as_array);
+        if excluded.is_some_and(|monitors| !monitors.is_empty()) {
+            return Err(ConfigError::Locked { field: EXCLUDED_MONITORS.to_string() });
+        }
+
+        Ok(())
+    }
+
+    /// 锁定信息（嵌套字段使用点号分隔的路径，如 "breaks.enabled"）
+    pub fn locked_settings(&self) -> LockedSettings {
+        let mut fields = Vec::new();
+        locked_paths(&self.policy.locked, "", &mut fields);
+        if self.policy.min_opacity.is_some() {
+            fields.push(EXCLUDED_MONITORS.to_string());
+        }
+        fields.sort();
+
+        LockedSettings {
+            fields,
+            min_opacity: self.policy.min_opacity,
+        }
+    }
+}
+
+/// 合并系统配置层：嵌套对象逐层合并，其他值整体覆盖
+fn deep_merge(target: &mut Map<String, Value>, source: Map<String, Value>) {
+    for (key, value) in source {
+        if let (Some(Value::Object(slot)), Value::Object(nested)) = (target.get_mut(&key), &value) {
+            deep_merge(slot, nested.clone());
+            continue;
+        }
+        target.insert(key, value);
+    }
+}
+
+/// 字段路径，`prefix` 为空时即为字段名
+fn field_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// 逐层比较补丁与锁定值，返回第一个被修改的锁定字段
+fn check_locked(locked: &Map<String, Value>, fields: &Map<String, Value>, prefix: &str) -> Result<(), ConfigError> {
+    for (key, field) in fields {
+        let Some(locked) = locked.get(key) else {
+            continue;
+        };
+        let path = field_path(prefix, key);
+        match (locked, field) {
+            (Value::Object(locked), Value::Object(nested)) => check_locked(locked, nested, &path)?,
+            _ if locked != field => return Err(ConfigError::Locked { field: path }),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 被锁定的字段路径（嵌套对象展开到最内层）
+fn locked_paths(locked: &Map<String, Value>, prefix: &str, paths: &mut Vec<String>) {
+    for (key, value) in locked {
+        let path = field_path(prefix, key);
+        match value {
+            Value::Object(nested) => locked_paths(nested, &path, paths),
+            _ => paths.push(path),
+        }
+    }
+}
+
+/// 读取 JSON 对象文件
+fn read_object(path: &Path) -> Option<Map<String, Value>> {
+    let content = fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<Value>(&content).ok()? {
+        Value::Object(object) => Some(object),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers_with_policy(policy: Value) -> SystemLayers {
+        SystemLayers {
+            defaults: Map::new(),
+            policy: serde_json::from_value(policy).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_enforce_policy() {
+        let layers = layers_with_policy(json!({
+            "locked": { "enabled": true },
+            "min_opacity": 0.5
+        }));
+
+        let mut value = json!({ "enabled": false, "opacity": 0.2 });
+        layers.enforce(&mut value);
+        assert_eq!(value["enabled"], true);
+        assert_eq!(value["opacity"], 0.5);
+    }
+
+    #[test]
+    fn test_check_patch() {
+        let layers = layers_with_policy(json!({
+            "locked": { "enabled": true },
+            "min_opacity": 0.5
+        }));
+
+        let patch = json!({ "enabled": false });
+        assert_eq!(
+            layers.check_patch(patch.as_object().unwrap()),
+            Err(ConfigError::Locked { field: "enabled".to_string() })
+        );
+
+        let patch = json!({ "opacity": 0.3 });
+        assert!(matches!(
+            layers.check_patch(patch.as_object().unwrap()),
+            Err(ConfigError::OpacityBelowMinimum { .. })
+        ));
+
+        let patch = json!({ "enabled": true, "opacity": 0.7 });
+        assert!(layers.check_patch(patch.as_object().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_min_opacity_bypasses() {
+        let layers = layers_with_policy(json!({ "min_opacity": 0.5 }));
+
+        let mut value = json!({
+            "opacity": 0.6,
+            "gradual_dim": { "start_opacity": 0.1, "max_opacity": 0.8 },
+            "recent_monitors": { "count": 2, "rank_opacity": 0.0 },
+            "focus": { "opacity": 0.2 },
+            "excluded_monitors": ["HDMI-1"]
+        });
+        layers.enforce(&mut value);
+        assert_eq!(value["gradual_dim"]["start_opacity"], 0.5);
+        assert_eq!(value["gradual_dim"]["max_opacity"], 0.8);
+        assert_eq!(value["recent_monitors"]["rank_opacity"], 0.5);
+        assert_eq!(value["focus"]["opacity"], 0.5);
+        assert_eq!(value["excluded_monitors"], json!([]));
+
+        for patch in [
+            json!({ "gradual_dim": { "start_opacity": 0.1 } }),
+            json!({ "recent_monitors": { "rank_opacity": 0.0 } }),
+            json!({ "focus": { "opacity": 0.2 } }),
+        ] {
+            assert!(matches!(
+                layers.check_patch(patch.as_object().unwrap()),
+                Err(ConfigError::OpacityBelowMinimum { .. })
+            ));
+        }
+
+        let patch = json!({ "excluded_monitors": ["HDMI-1"] });
+        assert_eq!(
+            layers.check_patch(patch.as_object().unwrap()),
+            Err(ConfigError::Locked { field: "excluded_monitors".to_string() })
+        );
+        let patch = json!({ "excluded_monitors": [] });
+        assert!(layers.check_patch(patch.as_object().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_nested_lock() {
+        let layers = layers_with_policy(json!({ "locked": { "breaks": { "enabled": true } } }));
+
+        let mut value = json!({ "breaks": { "enabled": false, "interval_minutes": 30 } });
+        layers.enforce(&mut value);
+        assert_eq!(value["breaks"], json!({ "enabled": true, "interval_minutes": 30 }));
+
+        let patch = json!({ "breaks": { "interval_minutes": 45 } });
+        assert!(layers.check_patch(patch.as_object().unwrap()).is_ok());
+
+        let patch = json!({ "breaks": { "enabled": false } });
+        assert_eq!(
+            layers.check_patch(patch.as_object().unwrap()),
+            Err(ConfigError::Locked { field: "breaks.enabled".to_string() })
+        );
+        assert_eq!(layers.locked_settings().fields, ["breaks.enabled"]);
+    }
+
+    #[test]
+    fn test_merge_defaults_layers() {
+        let root = std::env::temp_dir().join(format!("monofocus-policy-{}", std::process::id()));
+        let (high, low) = (root.join("high"), root.join("low"));
+        fs::create_dir_all(&high).unwrap();
+        fs::create_dir_all(&low).unwrap();
+        fs::write(low.join(DEFAULTS_FILE), r#"{"breaks": {"enabled": true, "interval_minutes": 30}}"#).unwrap();
+        fs::write(high.join(DEFAULTS_FILE), r#"{"breaks": {"interval_minutes": 45}}"#).unwrap();
+
+        let layers = SystemLayers::load(&[high, low]);
+        assert_eq!(
+            Value::Object(layers.defaults),
+            json!({ "breaks": { "enabled": true, "interval_minutes": 45 } })
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_load_missing_layers() {
+        let layers = SystemLayers::load(&[PathBuf::from("/nonexistent/MonoFocus")]);
+        assert!(layers.defaults.is_empty());
+        assert_eq!(layers.policy, Policy::default());
+    }
+}
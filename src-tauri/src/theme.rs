@@ -0,0 +1,412 @@
+// 界面主题：解析配置中的 "auto" / "light" / "dark"，auto 时检测桌面的浅色/深色偏好
+//
+// Linux 依次检查 GNOME 的 color-scheme、GTK_THEME、GTK 的 settings.ini 和 KDE 的 kdeglobals；
+// Windows 读取 AppsUseLightTheme，macOS 读取 AppleInterfaceStyle。
+//
+// Linux 上通过 XDG 桌面门户的 SettingChanged 信号得知系统主题变化，没有门户时（以及其他平台）低频轮询。
+
+use crate::actions;
+use crate::config::AppConfig;
+use crate::tray;
+use serde::Serialize;
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 无法订阅系统主题变化时检查的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+#[cfg(target_os = "linux")]
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+#[cfg(target_os = "linux")]
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+#[cfg(target_os = "linux")]
+const PORTAL_SETTINGS: &str = "org.freedesktop.portal.Settings";
+#[cfg(target_os = "linux")]
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+#[cfg(target_os = "linux")]
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+
+/// 解析后的主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// 跟随主题时的遮罩颜色：深色主题为纯黑，浅色主题为柔和的深灰
+    pub fn overlay_color(self) -> [u8; 3] {
+        match self {
+            Theme::Light => [60, 60, 60],
+            Theme::Dark => [0, 0, 0],
+        }
+    }
+}
+
+/// theme-changed 事件的内容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThemeInfo {
+    /// 配置中的设置（"auto"、"light" 或 "dark"）
+    pub setting: String,
+    pub theme: Theme,
+}
+
+/// 当前生效的主题
+#[derive(Default)]
+pub struct ThemeState {
+    current: Mutex<Option<ThemeInfo>>,
+}
+
+/// 按配置解析主题，auto 时检测系统设置（检测不到时为浅色）
+pub fn resolve_theme(setting: &str) -> Theme {
+    match setting {
+        "light" => Theme::Light,
+        "dark" => Theme::Dark,
+        _ => detect_system_theme().unwrap_or(Theme::Light),
+    }
+}
+
+/// 当前生效的主题（尚未解析时按配置解析）
+pub fn current_theme(app: &AppHandle, config: &AppConfig) -> Theme {
+    app.try_state::<ThemeState>()
+        .and_then(|state| state.current.lock().unwrap().as_ref().map(|info| info.theme))
+        .unwrap_or_else(|| resolve_theme(&config.theme))
+}
+
+/// 当前的主题设置和解析结果
+pub fn theme_info(app: &AppHandle, config: &AppConfig) -> ThemeInfo {
+    ThemeInfo {
+        setting: config.theme.clone(),
+        theme: current_theme(app, config),
+    }
+}
+
+/// 遮罩颜色：未开启 theme_overlay 时始终为黑色
+pub fn overlay_color(config: &AppConfig, theme: Theme) -> [u8; 3] {
+    if config.theme_overlay {
+        theme.overlay_color()
+    } else {
+        [0, 0, 0]
+    }
+}
+
+/// 主题设置变化时重新解析（系统主题的变化由 start_theme_watcher 检测），返回是否变化
+pub fn update_theme(app: &AppHandle, config: &AppConfig) -> bool {
+    let unchanged = app
+        .try_state::<ThemeState>()
+        .and_then(|state| state.current.lock().unwrap().as_ref().map(|info| info.setting == config.theme))
+        .unwrap_or(false);
+    if unchanged {
+        return false;
+    }
+    set_theme(app, config, resolve_theme(&config.theme))
+}
+
+/// 保存主题，变化时发送 theme-changed，返回是否变化
+fn set_theme(app: &AppHandle, config: &AppConfig, theme: Theme) -> bool {
+    let Some(state) = app.try_state::<ThemeState>() else {
+        return false;
+    };
+
+    let info = ThemeInfo {
+        setting: config.theme.clone(),
+        theme,
+    };
+    let previous = state.current.lock().unwrap().replace(info.clone());
+    if previous.as_ref() == Some(&info) {
+        return false;
+    }
+
+    let _ = app.emit_all("theme-changed", info);
+    true
+}
+
+/// 启动主题监听线程：跟随系统时，系统切换浅色/深色后更新遮罩和托盘图标
+pub fn start_theme_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        // 门户可用时阻塞在信号上，只有连接失败或断开后才回退到轮询
+        #[cfg(target_os = "linux")]
+        if let Err(e) = watch_portal(&app) {
+            eprintln!("Failed to watch the desktop portal for theme changes: {}", e);
+        }
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            system_theme_changed(&app, None);
+        }
+    });
+}
+
+/// 系统主题可能已变化：跟随系统时更新主题（theme 为 None 时重新检测）
+fn system_theme_changed(app: &AppHandle, theme: Option<Theme>) {
+    let config = actions::load_config(app);
+    if config.theme != "auto" {
+        return;
+    }
+
+    let theme = theme.unwrap_or_else(|| resolve_theme(&config.theme));
+    if set_theme(app, &config, theme) {
+        actions::refresh_overlays(app, &config);
+        tray::refresh_tray(app, &config);
+    }
+}
+
+/// 订阅门户的 SettingChanged 信号，直到连接断开
+#[cfg(target_os = "linux")]
+fn watch_portal(app: &AppHandle) -> zbus::Result<()> {
+    use zbus::blocking::{Connection, Proxy, ProxyBuilder};
+    use zbus::zvariant::OwnedValue;
+    use zbus::CacheProperties;
+
+    let connection = Connection::session()?;
+    let proxy: Proxy = ProxyBuilder::new_bare(&connection)
+        .destination(PORTAL_DESTINATION)?
+        .path(PORTAL_PATH)?
+        .interface(PORTAL_SETTINGS)?
+        .cache_properties(CacheProperties::No)
+        .build()?;
+    let signals = proxy.receive_signal_with_args("SettingChanged", &[(0, APPEARANCE_NAMESPACE)])?;
+
+    // 先订阅再读取一次，确认门户提供 color-scheme（否则回退到轮询）
+    let _: OwnedValue = proxy.call("Read", &(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY))?;
+
+    for message in signals {
+        let Ok((_, key, value)) = message.body::<(String, String, OwnedValue)>() else {
+            continue;
+        };
+        if key == COLOR_SCHEME_KEY {
+            // 没有偏好时按其他来源重新检测
+            system_theme_changed(app, parse_portal_color_scheme(&value));
+        }
+    }
+    Ok(())
+}
+
+/// 门户的 color-scheme：1 为深色，2 为浅色，0 表示没有偏好
+#[cfg(target_os = "linux")]
+fn parse_portal_color_scheme(value: &zbus::zvariant::Value) -> Option<Theme> {
+    use zbus::zvariant::Value;
+
+    match value {
+        Value::U32(1) => Some(Theme::Dark),
+        Value::U32(2) => Some(Theme::Light),
+        // Read 的返回值多包了一层 variant
+        Value::Value(inner) => parse_portal_color_scheme(inner),
+        _ => None,
+    }
+}
+
+/// 检测系统的浅色/深色偏好，无法判断时返回 None
+#[cfg(target_os = "linux")]
+pub fn detect_system_theme() -> Option<Theme> {
+    let color_scheme = command_output("gsettings", &["get", "org.gnome.desktop.interface", "color-scheme"]);
+    if let Some(theme) = color_scheme.as_deref().and_then(parse_color_scheme) {
+        return Some(theme);
+    }
+
+    if let Some(theme) = std::env::var("GTK_THEME").ok().as_deref().and_then(parse_theme_name) {
+        return Some(theme);
+    }
+
+    let config_dir = crate::autostart::xdg_config_home().ok()?;
+    for version in ["gtk-4.0", "gtk-3.0"] {
+        let path = config_dir.join(version).join("settings.ini");
+        if let Some(theme) = read_file(&path).as_deref().and_then(parse_gtk_settings) {
+            return Some(theme);
+        }
+    }
+
+    read_file(&config_dir.join("kdeglobals"))
+        .as_deref()
+        .and_then(parse_kdeglobals)
+}
+
+#[cfg(target_os = "windows")]
+pub fn detect_system_theme() -> Option<Theme> {
+    let output = command_output(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+            "/v",
+            "AppsUseLightTheme",
+        ],
+    )?;
+    let value = output.split_whitespace().last()?;
+    match value {
+        "0x0" => Some(Theme::Dark),
+        "0x1" => Some(Theme::Light),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn detect_system_theme() -> Option<Theme> {
+    // 浅色模式下没有 AppleInterfaceStyle，命令会失败
+    match command_output("defaults", &["read", "-g", "AppleInterfaceStyle"]) {
+        Some(style) if style.trim() == "Dark" => Some(Theme::Dark),
+        _ => Some(Theme::Light),
+    }
+}
+
+/// 执行命令并返回标准输出，失败时返回 None
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+
+    // 避免每次检测都弹出控制台窗口
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(target_os = "linux")]
+fn read_file(path: &std::path::Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// GNOME 的 color-scheme，例如 'prefer-dark'；'default' 表示没有偏好
+#[cfg(target_os = "linux")]
+fn parse_color_scheme(value: &str) -> Option<Theme> {
+    match value.trim().trim_matches('\'') {
+        "prefer-dark" => Some(Theme::Dark),
+        "prefer-light" => Some(Theme::Light),
+        _ => None,
+    }
+}
+
+/// 主题名称，例如 "Adwaita-dark"、"Adwaita:dark"
+#[cfg(target_os = "linux")]
+fn parse_theme_name(name: &str) -> Option<Theme> {
+    let name = name.trim();
+    if name.is_empty() {
+        None
+    } else if name.to_lowercase().contains("dark") {
+        Some(Theme::Dark)
+    } else {
+        Some(Theme::Light)
+    }
+}
+
+/// 从 ini 文件中读取指定分组下的键
+#[cfg(target_os = "linux")]
+fn ini_value<'a>(content: &'a str, section: &str, key: &str) -> Option<&'a str> {
+    let mut in_section = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line == format!("[{}]", section);
+        } else if in_section {
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim() == key {
+                    return Some(v.trim());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// GTK 的 settings.ini：gtk-application-prefer-dark-theme 优先，其次是主题名称
+#[cfg(target_os = "linux")]
+fn parse_gtk_settings(content: &str) -> Option<Theme> {
+    if matches!(
+        ini_value(content, "Settings", "gtk-application-prefer-dark-theme"),
+        Some("1") | Some("true")
+    ) {
+        return Some(Theme::Dark);
+    }
+    ini_value(content, "Settings", "gtk-theme-name").and_then(parse_theme_name)
+}
+
+/// KDE 的 kdeglobals：按窗口背景色的亮度判断，其次是配色方案名称
+#[cfg(target_os = "linux")]
+fn parse_kdeglobals(content: &str) -> Option<Theme> {
+    if let Some(background) = ini_value(content, "Colors:Window", "BackgroundNormal") {
+        let rgb: Vec<f32> = background
+            .split(',')
+            .filter_map(|c| c.trim().parse().ok())
+            .collect();
+        if let [r, g, b] = rgb[..] {
+            let luminance = (0.299 * r + 0.587 * g + 0.114 * b) / 255.0;
+            return Some(if luminance < 0.5 { Theme::Dark } else { Theme::Light });
+        }
+    }
+    ini_value(content, "General", "ColorScheme").and_then(parse_theme_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_color_scheme() {
+        assert_eq!(parse_color_scheme("'prefer-dark'\n"), Some(Theme::Dark));
+        assert_eq!(parse_color_scheme("'prefer-light'"), Some(Theme::Light));
+        assert_eq!(parse_color_scheme("'default'"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_portal_color_scheme() {
+        use zbus::zvariant::Value;
+
+        assert_eq!(parse_portal_color_scheme(&Value::U32(1)), Some(Theme::Dark));
+        assert_eq!(parse_portal_color_scheme(&Value::U32(2)), Some(Theme::Light));
+        assert_eq!(parse_portal_color_scheme(&Value::U32(0)), None);
+        let nested = Value::Value(Box::new(Value::U32(1)));
+        assert_eq!(parse_portal_color_scheme(&nested), Some(Theme::Dark));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_gtk_settings() {
+        let content = "[Settings]\ngtk-theme-name=Adwaita\ngtk-application-prefer-dark-theme=1\n";
+        assert_eq!(parse_gtk_settings(content), Some(Theme::Dark));
+
+        let content = "[Settings]\ngtk-theme-name = Arc-Dark\n";
+        assert_eq!(parse_gtk_settings(content), Some(Theme::Dark));
+
+        let content = "[Settings]\ngtk-application-prefer-dark-theme=false\ngtk-theme-name=Adwaita\n";
+        assert_eq!(parse_gtk_settings(content), Some(Theme::Light));
+
+        assert_eq!(parse_gtk_settings("[Other]\ngtk-theme-name=Adwaita-dark\n"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_kdeglobals() {
+        let content = "[General]\nColorScheme=BreezeLight\n\n[Colors:Window]\nBackgroundNormal=32,35,38\n";
+        assert_eq!(parse_kdeglobals(content), Some(Theme::Dark));
+
+        let content = "[General]\nColorScheme=BreezeDark\n";
+        assert_eq!(parse_kdeglobals(content), Some(Theme::Dark));
+
+        let content = "[Colors:Window]\nBackgroundNormal=239,240,241\n";
+        assert_eq!(parse_kdeglobals(content), Some(Theme::Light));
+    }
+
+    #[test]
+    fn test_overlay_color() {
+        let mut config = AppConfig::default();
+        assert_eq!(overlay_color(&config, Theme::Light), [0, 0, 0]);
+
+        config.theme_overlay = true;
+        assert_eq!(overlay_color(&config, Theme::Light), Theme::Light.overlay_color());
+        assert_eq!(overlay_color(&config, Theme::Dark), [0, 0, 0]);
+        assert_eq!(resolve_theme("dark"), Theme::Dark);
+    }
+}
@@ -1,31 +1,256 @@
+use crate::breaks::{self, BreakStatus};
+use crate::config::AppConfig;
+use crate::focus::{self, FocusPhase};
+use crate::i18n::{t, tf};
+use crate::monitor::{get_monitors, MonitorInfo};
+use crate::pause::{self, now_secs, remaining_minutes, PauseDuration};
+use crate::theme::{self, Theme};
+use crate::AppState;
+use serde_json::json;
+use std::sync::Mutex;
 use tauri::{
-    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
-    SystemTrayMenuItem,
+    AppHandle, CustomMenuItem, Icon, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem, SystemTraySubmenu,
 };
 
-pub fn create_tray() -> SystemTray {
-    let enable = CustomMenuItem::new("toggle".to_string(), "护眼模式：开启");
-    let settings = CustomMenuItem::new("settings".to_string(), "设置");
-    let check_update = CustomMenuItem::new("check_update".to_string(), "检查更新");
-    let quit = CustomMenuItem::new("quit".to_string(), "退出");
+/// 托盘快速调整的透明度（百分比）
+const OPACITY_STEPS: &[u32] = &[20, 40, 60, 80, 95];
+
+/// 托盘快速调整的动画时长（毫秒）和文本键，与设置窗口中的选项一致
+const ANIMATION_STEPS: &[(u64, &str)] = &[
+    (0, "animNone"),
+    (200, "animFast"),
+    (300, "animMedium"),
+    (500, "animSlow"),
+];
+
+/// 托盘暂停选项的菜单 ID 和文本键
+const PAUSE_ITEMS: &[(&str, &str)] = &[
+    ("pause_15", "tray.pause15"),
+    ("pause_60", "tray.pause60"),
+    ("pause_tomorrow", "tray.pauseTomorrow"),
+];
+
+/// 托盘中休息子菜单的状态（未开启休息提醒时不显示）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BreakMenu {
+    /// 距离下次休息的分钟数
+    Next(u64),
+    Active,
+}
+
+impl BreakMenu {
+    fn from_status(status: &BreakStatus) -> Option<Self> {
+        if status.on_break {
+            Some(BreakMenu::Active)
+        } else {
+            status
+                .next_break_secs
+                .map(|secs| BreakMenu::Next(remaining_minutes(secs)))
+        }
+    }
+}
+
+/// 托盘中显示器的标题，例如 "HDMI-1 (1920×1080)"
+fn display_title(monitor: &MonitorInfo) -> String {
+    let name = if monitor.name.is_empty() {
+        &monitor.id
+    } else {
+        &monitor.name
+    };
+    format!("{} ({}×{})", name, monitor.width, monitor.height)
+}
+
+/// 锁定项中显示器的名称，没有名称时用序号（从 1 开始）
+fn monitor_label(monitors: &[MonitorInfo], monitor_id: &str) -> String {
+    match monitors.iter().position(|monitor| monitor.id == monitor_id) {
+        Some(index) if monitors[index].name.is_empty() => format!("#{}", index + 1),
+        Some(index) => monitors[index].name.clone(),
+        None => monitor_id.to_string(),
+    }
+}
+
+/// 按当前配置构建托盘菜单（当前的透明度和动画速度带勾选）
+fn build_menu(
+    config: &AppConfig,
+    locked_monitor: Option<&str>,
+    monitors: &[MonitorInfo],
+    break_menu: Option<BreakMenu>,
+    focus_remaining: Option<(FocusPhase, u64)>,
+) -> SystemTrayMenu {
+    let language = config.language.as_str();
+    let text = |key: &str| t(language, key);
+
+    let toggle_text = match config.paused_until {
+        Some(until) => {
+            let minutes = remaining_minutes(until.saturating_sub(now_secs()));
+            let remaining =
+                format_remaining(minutes, &text("tray.hourUnit"), &text("tray.minuteUnit"));
+            tf(language, "tray.togglePaused", &[("remaining", remaining)])
+        }
+        None if config.enabled => text("tray.toggleOn"),
+        None => text("tray.toggleOff"),
+    };
+    let enable = CustomMenuItem::new("toggle".to_string(), toggle_text);
+
+    let mut pause_menu = SystemTrayMenu::new();
+    for (id, key) in PAUSE_ITEMS {
+        pause_menu = pause_menu.add_item(CustomMenuItem::new(id.to_string(), text(key)));
+    }
+
+    let lock = match locked_monitor {
+        Some(monitor_id) => {
+            let label = format!("{} ({})", text("tray.locked"), monitor_label(monitors, monitor_id));
+            CustomMenuItem::new("lock".to_string(), label).selected()
+        }
+        None => CustomMenuItem::new("lock".to_string(), text("tray.lock")),
+    };
+
+    let current_opacity = (config.opacity * 100.0).round() as u32;
+    let mut opacity_menu = SystemTrayMenu::new();
+    for &step in OPACITY_STEPS {
+        let mut item = CustomMenuItem::new(format!("opacity_{}", step), format!("{}%", step));
+        if step == current_opacity {
+            item = item.selected();
+        }
+        opacity_menu = opacity_menu.add_item(item);
+    }
 
-    let tray_menu = SystemTrayMenu::new()
+    let mut animation_menu = SystemTrayMenu::new();
+    for &(duration, key) in ANIMATION_STEPS {
+        let mut item = CustomMenuItem::new(format!("animation_{}", duration), text(key));
+        if duration == config.animation_duration {
+            item = item.selected();
+        }
+        animation_menu = animation_menu.add_item(item);
+    }
+
+    // 每个显示器一个子菜单：排除遮罩、锁定焦点
+    let mut displays_menu = SystemTrayMenu::new();
+    for monitor in monitors {
+        let mut exclude = CustomMenuItem::new(format!("exclude_{}", monitor.id), text("tray.displayExclude"));
+        if config.excluded_monitors.contains(&monitor.name) {
+            exclude = exclude.selected();
+        }
+        let mut lock = CustomMenuItem::new(format!("lock_{}", monitor.id), text("tray.displayLock"));
+        if locked_monitor == Some(monitor.id.as_str()) {
+            lock = lock.selected();
+        }
+
+        let monitor_menu = SystemTrayMenu::new().add_item(exclude).add_item(lock);
+        displays_menu =
+            displays_menu.add_submenu(SystemTraySubmenu::new(display_title(monitor), monitor_menu));
+    }
+
+    let settings = CustomMenuItem::new("settings".to_string(), text("tray.settings"));
+
+    let mut menu = SystemTrayMenu::new()
         .add_item(enable)
+        .add_item(lock)
+        .add_submenu(SystemTraySubmenu::new(text("tray.pause"), pause_menu));
+
+    // 专注时段：进行中时显示剩余时间
+    menu = match focus_remaining {
+        Some((phase, minutes)) => {
+            let key = match phase {
+                FocusPhase::Focus => "tray.focusRemaining",
+                FocusPhase::Break => "tray.focusBreak",
+            };
+            let remaining =
+                format_remaining(minutes, &text("tray.hourUnit"), &text("tray.minuteUnit"));
+            let status = tf(language, key, &[("remaining", remaining)]);
+            menu.add_item(CustomMenuItem::new("focus_status".to_string(), status).disabled())
+                .add_item(CustomMenuItem::new("focus_stop".to_string(), text("tray.focusStop")))
+        }
+        None => menu.add_item(CustomMenuItem::new(
+            "focus_start".to_string(),
+            tf(
+                language,
+                "tray.focusStart",
+                &[("minutes", config.focus.focus_minutes.to_string())],
+            ),
+        )),
+    };
+
+    if let Some(break_menu) = break_menu {
+        let status = match break_menu {
+            BreakMenu::Next(minutes) => {
+                tf(language, "tray.breakNext", &[("minutes", minutes.to_string())])
+            }
+            BreakMenu::Active => text("tray.breakActive"),
+        };
+        let snooze = tf(
+            language,
+            "tray.breakSnooze",
+            &[("minutes", config.breaks.snooze_minutes.to_string())],
+        );
+        let mut breaks_menu = SystemTrayMenu::new()
+            .add_item(CustomMenuItem::new("break_status".to_string(), status).disabled());
+        // 休息中可以推迟或跳过，否则可以立即休息
+        breaks_menu = match break_menu {
+            BreakMenu::Active => breaks_menu
+                .add_item(CustomMenuItem::new("break_snooze".to_string(), snooze))
+                .add_item(CustomMenuItem::new("break_skip".to_string(), text("tray.breakSkip"))),
+            BreakMenu::Next(_) => breaks_menu
+                .add_item(CustomMenuItem::new("break_now".to_string(), text("tray.breakNow"))),
+        };
+        menu = menu.add_submenu(SystemTraySubmenu::new(text("tray.breaks"), breaks_menu));
+    }
+
+    let check_update = CustomMenuItem::new("check_update".to_string(), text("tray.checkUpdate"));
+    let quit = CustomMenuItem::new("quit".to_string(), text("tray.quit"));
+
+    menu.add_submenu(SystemTraySubmenu::new(text("opacity"), opacity_menu))
+        .add_submenu(SystemTraySubmenu::new(text("animation"), animation_menu))
+        .add_submenu(SystemTraySubmenu::new(text("tray.displays"), displays_menu))
         .add_native_item(SystemTrayMenuItem::Separator)
         .add_item(settings)
         .add_item(check_update)
         .add_native_item(SystemTrayMenuItem::Separator)
-        .add_item(quit);
+        .add_item(quit)
+}
 
-    SystemTray::new().with_menu(tray_menu)
+pub fn create_tray() -> SystemTray {
+    SystemTray::new().with_menu(build_menu(&AppConfig::default(), None, &[], None, None))
 }
 
 pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
     match event {
         SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
             "toggle" => {
-                // 切换护眼模式
-                app.emit_all("toggle-shield", ()).unwrap();
+                // 切换护眼模式：直接在后端保存并应用，前端通过 config-changed 同步
+                if let Err(e) = crate::actions::toggle(app) {
+                    crate::actions::report_error(app, &e);
+                }
+            }
+            "pause_15" | "pause_60" | "pause_tomorrow" => {
+                let duration = match id.as_str() {
+                    "pause_15" => PauseDuration::Minutes(15),
+                    "pause_60" => PauseDuration::Minutes(60),
+                    _ => PauseDuration::UntilTomorrow,
+                };
+                if let Err(e) = pause::pause(app, duration) {
+                    crate::actions::report_error(app, &e);
+                }
+            }
+            "focus_start" => {
+                focus::start_session(app);
+            }
+            "focus_stop" => {
+                focus::stop_session(app);
+            }
+            "break_now" => {
+                breaks::start_break(app);
+            }
+            "break_snooze" => {
+                breaks::snooze_break(app);
+            }
+            "break_skip" => {
+                breaks::skip_break(app);
+            }
+            "lock" => {
+                // 锁定/解除锁定当前显示器
+                crate::actions::toggle_focus_lock(app);
             }
             "settings" => {
                 // 显示主窗口
@@ -36,13 +261,47 @@ pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
             }
             "check_update" => {
                 // 检查更新
-                app.emit_all("check-update", ()).unwrap();
+                let _ = app.emit_all("check-update", ());
             }
             "quit" => {
                 // 退出应用
                 app.exit(0);
             }
-            _ => {}
+            id => {
+                // 透明度、动画速度和显示器子菜单
+                let result = if let Some(percent) = id.strip_prefix("opacity_") {
+                    match percent.parse::<f32>() {
+                        Ok(percent) => crate::actions::set_opacity(app, percent / 100.0),
+                        Err(_) => Ok(()),
+                    }
+                } else if let Some(duration) = id.strip_prefix("animation_") {
+                    match duration.parse::<u64>() {
+                        Ok(duration) => crate::actions::patch_and_apply(
+                            app,
+                            json!({ "animation_duration": duration }),
+                        )
+                        .map(|_| ()),
+                        Err(_) => Ok(()),
+                    }
+                } else if let Some(monitor_id) = id.strip_prefix("exclude_") {
+                    crate::actions::toggle_monitor_excluded(app, monitor_id)
+                } else if let Some(monitor_id) = id.strip_prefix("lock_") {
+                    // 再次点击已锁定的显示器时解除锁定
+                    let locked = app.state::<AppState>().locked_monitor_id.lock().unwrap().clone();
+                    let target = match locked {
+                        Some(locked) if locked == monitor_id => None,
+                        _ => Some(monitor_id.to_string()),
+                    };
+                    crate::actions::set_focus_lock(app, target);
+                    Ok(())
+                } else {
+                    Ok(())
+                };
+
+                if let Err(e) = result {
+                    crate::actions::report_error(app, &e);
+                }
+            }
         },
         SystemTrayEvent::LeftClick { .. } => {
             // 左键点击显示主窗口
@@ -55,26 +314,282 @@ pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
     }
 }
 
-/// 更新托盘菜单项文本（根据状态和语言）
-pub fn update_tray_menu_text(app: &AppHandle, enabled: bool, language: &str) {
-    let toggle_tray = app.tray_handle().get_item("toggle");
-    let settings_tray = app.tray_handle().get_item("settings");
-    let check_update_tray = app.tray_handle().get_item("check_update");
-    let quit_tray = app.tray_handle().get_item("quit");
-    
-    let (toggle_on, toggle_off, settings_text, check_update_text, quit_text) = match language {
-        "en" => ("Eye Care Mode: ON", "Eye Care Mode: OFF", "Settings", "Check for Updates", "Exit"),
-        "ja" => ("アイケアモード：オン", "アイケアモード：オフ", "設定", "更新を確認", "終了"),
-        "fr" => ("Mode protection: ACTIVÉ", "Mode protection: DÉSACTIVÉ", "Paramètres", "Vérifier les mises à jour", "Quitter"),
-        "de" => ("Augenschutzmodus: AN", "Augenschutzmodus: AUS", "Einstellungen", "Nach Updates suchen", "Beenden"),
-        "es" => ("Modo protección: ACTIVADO", "Modo protección: DESACTIVADO", "Ajustes", "Buscar actualizaciones", "Salir"),
-        _ => ("护眼模式：开启", "护眼模式：关闭", "设置", "检查更新", "退出"), // 默认中文
+/// 托盘状态，决定显示的图标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayStatus {
+    Enabled,
+    Disabled,
+    Paused,
+    Locked,
+}
+
+impl TrayStatus {
+    /// 暂停优先于关闭，锁定只在开启时显示
+    pub fn from_config(config: &AppConfig, locked: bool) -> Self {
+        if config.paused_until.is_some() {
+            TrayStatus::Paused
+        } else if !config.enabled {
+            TrayStatus::Disabled
+        } else if locked {
+            TrayStatus::Locked
+        } else {
+            TrayStatus::Enabled
+        }
+    }
+
+    /// `dark` 为 true 时使用深色主题的浅色图标
+    fn icon(&self, dark: bool) -> Icon {
+        let bytes: &[u8] = match (self, dark) {
+            (TrayStatus::Enabled, false) => include_bytes!("../icons/tray-enabled.png"),
+            (TrayStatus::Disabled, false) => include_bytes!("../icons/tray-disabled.png"),
+            (TrayStatus::Paused, false) => include_bytes!("../icons/tray-paused.png"),
+            (TrayStatus::Locked, false) => include_bytes!("../icons/tray-locked.png"),
+            (TrayStatus::Enabled, true) => include_bytes!("../icons/tray-enabled-dark.png"),
+            (TrayStatus::Disabled, true) => include_bytes!("../icons/tray-disabled-dark.png"),
+            (TrayStatus::Paused, true) => include_bytes!("../icons/tray-paused-dark.png"),
+            (TrayStatus::Locked, true) => include_bytes!("../icons/tray-locked-dark.png"),
+        };
+        Icon::Raw(bytes.to_vec())
+    }
+}
+
+/// 决定菜单内容的字段，变化时才重建菜单
+#[derive(Debug, Clone, PartialEq)]
+struct MenuKey {
+    language: String,
+    enabled: bool,
+    paused_minutes: Option<u64>,
+    locked_monitor: Option<String>,
+    opacity: u32,
+    animation_duration: u64,
+    /// 显示器 ID 和标题，接入或移除显示器时重建菜单
+    monitors: Vec<(String, String)>,
+    excluded_monitors: Vec<String>,
+    breaks: Option<BreakMenu>,
+    snooze_minutes: u64,
+    focus: Option<(FocusPhase, u64)>,
+    focus_minutes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rendered {
+    menu: MenuKey,
+    status: TrayStatus,
+    dark_icon: bool,
+    tooltip: String,
+}
+
+/// 上次刷新的托盘内容
+#[derive(Default)]
+pub struct TrayState {
+    rendered: Mutex<Option<Rendered>>,
+}
+
+/// 托盘提示：当前显示器（monitor_label）和透明度，专注时段进行中时附带剩余时间
+fn tooltip_text(
+    config: &AppConfig,
+    active_label: Option<&str>,
+    focus_remaining: Option<(FocusPhase, u64)>,
+) -> String {
+    let mut text = format!(
+        "MonoFocus · {} · {}%",
+        active_label.unwrap_or("-"),
+        (config.opacity * 100.0).round()
+    );
+    if let Some((phase, minutes)) = focus_remaining {
+        let icon = match phase {
+            FocusPhase::Focus => "🍅",
+            FocusPhase::Break => "☕",
+        };
+        let language = config.language.as_str();
+        let remaining = format_remaining(
+            minutes,
+            &t(language, "tray.hourUnit"),
+            &t(language, "tray.minuteUnit"),
+        );
+        text.push_str(&format!(" · {} {}", icon, remaining));
+    }
+    text
+}
+
+/// 刷新托盘的菜单、图标和提示
+///
+/// 配置、暂停、锁定和当前显示器的变化都通过这里更新托盘，只修改有变化的部分。
+/// 子菜单的标题无法单独修改，因此菜单变化时整体重建。
+pub fn refresh_tray(app: &AppHandle, config: &AppConfig) {
+    let (active_monitor, locked_monitor) = match app.try_state::<AppState>() {
+        Some(state) => (
+            state.current_monitor_id.lock().unwrap().clone(),
+            state.locked_monitor_id.lock().unwrap().clone(),
+        ),
+        None => (None, None),
     };
-    
-    let toggle_text = if enabled { toggle_on } else { toggle_off };
-    let _ = toggle_tray.set_title(toggle_text);
-    let _ = settings_tray.set_title(settings_text);
-    let _ = check_update_tray.set_title(check_update_text);
-    let _ = quit_tray.set_title(quit_text);
+
+    let monitors = get_monitors();
+    let break_menu = BreakMenu::from_status(&breaks::break_status(app, config));
+    let focus_remaining = focus::phase_remaining(app);
+    // 与锁定项一样显示显示器名称
+    let active_label = active_monitor.as_deref().map(|id| monitor_label(&monitors, id));
+
+    let rendered = Rendered {
+        menu: MenuKey {
+            language: config.language.clone(),
+            enabled: config.enabled,
+            paused_minutes: config
+                .paused_until
+                .map(|until| remaining_minutes(until.saturating_sub(now_secs()))),
+            locked_monitor: locked_monitor.clone(),
+            opacity: (config.opacity * 100.0).round() as u32,
+            animation_duration: config.animation_duration,
+            monitors: monitors
+                .iter()
+                .map(|monitor| (monitor.id.clone(), display_title(monitor)))
+                .collect(),
+            excluded_monitors: config.excluded_monitors.clone(),
+            breaks: break_menu,
+            snooze_minutes: config.breaks.snooze_minutes,
+            focus: focus_remaining,
+            focus_minutes: config.focus.focus_minutes,
+        },
+        status: TrayStatus::from_config(config, locked_monitor.is_some()),
+        dark_icon: config.theme_tray_icon && theme::current_theme(app, config) == Theme::Dark,
+        tooltip: tooltip_text(config, active_label.as_deref(), focus_remaining),
+    };
+
+    let tray_state = app.try_state::<TrayState>();
+    let previous = tray_state
+        .as_ref()
+        .and_then(|state| state.rendered.lock().unwrap().clone());
+
+    let tray = app.tray_handle();
+    if previous.as_ref().map(|p| &p.menu) != Some(&rendered.menu) {
+        let _ = tray.set_menu(build_menu(
+            config,
+            locked_monitor.as_deref(),
+            &monitors,
+            break_menu,
+            focus_remaining,
+        ));
+    }
+    if previous.as_ref().map(|p| (p.status, p.dark_icon)) != Some((rendered.status, rendered.dark_icon)) {
+        let _ = tray.set_icon(rendered.status.icon(rendered.dark_icon));
+    }
+    if previous.as_ref().map(|p| &p.tooltip) != Some(&rendered.tooltip) {
+        let _ = tray.set_tooltip(&rendered.tooltip);
+    }
+
+    if let Some(state) = tray_state {
+        *state.rendered.lock().unwrap() = Some(rendered);
+    }
+}
+
+/// 格式化剩余时间，例如 "1 h 5 min"
+fn format_remaining(minutes: u64, hour_unit: &str, minute_unit: &str) -> String {
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    match (hours, minutes) {
+        (0, minutes) => format!("{} {}", minutes, minute_unit),
+        (hours, 0) => format!("{} {}", hours, hour_unit),
+        (hours, minutes) => format!("{} {} {} {}", hours, hour_unit, minutes, minute_unit),
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(15, "h", "min"), "15 min");
+        assert_eq!(format_remaining(60, "h", "min"), "1 h");
+        assert_eq!(format_remaining(125, "h", "min"), "2 h 5 min");
+    }
+
+    #[test]
+    fn test_tray_status() {
+        let mut config = AppConfig::default();
+        assert_eq!(TrayStatus::from_config(&config, false), TrayStatus::Enabled);
+        assert_eq!(TrayStatus::from_config(&config, true), TrayStatus::Locked);
+
+        config.enabled = false;
+        assert_eq!(TrayStatus::from_config(&config, true), TrayStatus::Disabled);
+
+        config.paused_until = Some(1);
+        assert_eq!(TrayStatus::from_config(&config, true), TrayStatus::Paused);
+    }
+
+    #[test]
+    fn test_break_menu() {
+        let mut status = BreakStatus {
+            enabled: true,
+            on_break: false,
+            remaining_secs: None,
+            next_break_secs: Some(61),
+        };
+        assert_eq!(BreakMenu::from_status(&status), Some(BreakMenu::Next(2)));
+
+        status.on_break = true;
+        assert_eq!(BreakMenu::from_status(&status), Some(BreakMenu::Active));
+
+        status = BreakStatus {
+            enabled: false,
+            on_break: false,
+            remaining_secs: None,
+            next_break_secs: None,
+        };
+        assert_eq!(BreakMenu::from_status(&status), None);
+    }
+
+    #[test]
+    fn test_display_title() {
+        let mut monitor = MonitorInfo {
+            id: "monitor_1".to_string(),
+            name: "HDMI-1".to_string(),
+            x: 1920,
+            y: 0,
+            width: 2560,
+            height: 1440,
+            physical_width_mm: None,
+            physical_height_mm: None,
+            scale_factor: 1.0,
+        };
+        assert_eq!(display_title(&monitor), "HDMI-1 (2560×1440)");
+
+        monitor.name = String::new();
+        assert_eq!(display_title(&monitor), "monitor_1 (2560×1440)");
+    }
+
+    #[test]
+    fn test_monitor_label() {
+        let monitor = |id: &str, name: &str| MonitorInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080,
+            physical_width_mm: None,
+            physical_height_mm: None,
+            scale_factor: 1.0,
+        };
+        let monitors = [monitor("monitor_0", "eDP-1"), monitor("monitor_1", "")];
+        assert_eq!(monitor_label(&monitors, "monitor_0"), "eDP-1");
+        assert_eq!(monitor_label(&monitors, "monitor_1"), "#2");
+        assert_eq!(monitor_label(&monitors, "monitor_9"), "monitor_9");
+    }
+
+    #[test]
+    fn test_tooltip_text() {
+        let config = AppConfig::default();
+        assert_eq!(tooltip_text(&config, Some("HDMI-1"), None), "MonoFocus · HDMI-1 · 60%");
+        assert_eq!(tooltip_text(&config, None, None), "MonoFocus · - · 60%");
+
+        let config = AppConfig {
+            language: "en".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tooltip_text(&config, None, Some((FocusPhase::Focus, 12))),
+            "MonoFocus · - · 60% · 🍅 12 min"
+        );
+    }
+}
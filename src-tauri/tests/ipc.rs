@@ -0,0 +1,170 @@
+// 控制 socket 的集成测试：以 MockController 启动服务，用客户端收发 JSON-RPC 请求
+#![cfg(unix)]
+
+use monofocus_lib::actions::Controller;
+use monofocus_lib::cli::Status;
+use monofocus_lib::config::{AppConfig, ConfigError};
+use monofocus_lib::ipc::{call, start_ipc_server, Subscribers};
+use monofocus_lib::monitor::MonitorInfo;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 只修改内存中配置的控制器
+#[derive(Default)]
+struct MockController {
+    config: Mutex<AppConfig>,
+}
+
+impl Controller for MockController {
+    fn get_config(&self) -> AppConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    fn set_opacity(&self, opacity: f32) -> Result<(), ConfigError> {
+        self.config.lock().unwrap().opacity = opacity.clamp(0.0, 1.0);
+        Ok(())
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        self.config.lock().unwrap().enabled = enabled;
+        Ok(())
+    }
+
+    fn toggle(&self) -> Result<bool, ConfigError> {
+        let mut config = self.config.lock().unwrap();
+        config.enabled = !config.enabled;
+        Ok(config.enabled)
+    }
+
+    fn apply_preset(&self, name: &str) -> Result<(), ConfigError> {
+        let mut config = self.config.lock().unwrap();
+        let opacity = config
+            .find_preset(name)
+            .map(|preset| preset.opacity)
+            .ok_or_else(|| ConfigError::UnknownPreset {
+                name: name.to_string(),
+            })?;
+        config.opacity = opacity;
+        Ok(())
+    }
+
+    fn current_monitor(&self) -> Option<String> {
+        Some("monitor_0".to_string())
+    }
+
+    fn monitors(&self) -> Vec<MonitorInfo> {
+        Vec::new()
+    }
+
+    fn status(&self) -> Status {
+        Status::from_config(&self.get_config(), self.current_monitor())
+    }
+}
+
+struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Client {
+    fn connect(path: &Path) -> Self {
+        let stream = UnixStream::connect(path).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        Self {
+            writer: stream.try_clone().unwrap(),
+            reader: BufReader::new(stream),
+        }
+    }
+
+    fn call(&mut self, request: Value) -> Value {
+        writeln!(self.writer, "{}", request).unwrap();
+        self.read()
+    }
+
+    fn read(&mut self) -> Value {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+}
+
+fn start_test_server(name: &str) -> (PathBuf, Subscribers) {
+    let path = std::env::temp_dir().join(format!(
+        "monofocus-test-{}-{}.sock",
+        name,
+        std::process::id()
+    ));
+    let subscribers = Subscribers::default();
+    start_ipc_server(
+        path.clone(),
+        Arc::new(MockController::default()),
+        subscribers.clone(),
+    )
+    .unwrap();
+    (path, subscribers)
+}
+
+#[test]
+fn test_socket_requests() {
+    let (path, _) = start_test_server("requests");
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    let mut client = Client::connect(&path);
+    let response = client.call(json!({
+        "jsonrpc": "2.0", "id": 1, "method": "update_opacity", "params": { "opacity": 0.4 }
+    }));
+    assert_eq!(response["id"], 1);
+    assert!(response.get("error").is_none());
+
+    let response = client.call(json!({ "jsonrpc": "2.0", "id": 2, "method": "get_config" }));
+    assert_eq!(response["result"]["opacity"], 0.4f32 as f64);
+
+    let response = client.call(json!({ "jsonrpc": "2.0", "id": 3, "method": "get_current_monitor" }));
+    assert_eq!(response["result"], "monitor_0");
+
+    let response = client.call(json!({ "jsonrpc": "2.0", "id": 4, "method": "reboot" }));
+    assert_eq!(response["error"]["code"], -32601);
+
+    // 命令行的 status 通过 get_status 查询
+    let status: Status = serde_json::from_value(call(&path, "get_status", Value::Null).unwrap()).unwrap();
+    assert_eq!(status.opacity, 0.4);
+    assert_eq!(status.active_monitor.as_deref(), Some("monitor_0"));
+    assert!(call(&path, "reboot", Value::Null).is_err());
+
+    // 已有实例在监听时不能重复绑定
+    let result = start_ipc_server(
+        path.clone(),
+        Arc::new(MockController::default()),
+        Subscribers::default(),
+    );
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AddrInUse);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_socket_subscription() {
+    let (path, subscribers) = start_test_server("subscribe");
+
+    let mut client = Client::connect(&path);
+    let response = client.call(json!({ "jsonrpc": "2.0", "id": 1, "method": "subscribe" }));
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["result"], true);
+
+    subscribers.publish("monitor-changed", json!({ "monitor_id": "monitor_1" }));
+    let notification = client.read();
+    assert_eq!(notification["method"], "monitor-changed");
+    assert_eq!(notification["params"]["monitor_id"], "monitor_1");
+
+    fs::remove_file(&path).unwrap();
+}
@@ -1,135 +1,55 @@
 // 多语言翻译
-const translations = {
-  zh: {
-    eyeCareMode: "护眼模式",
-    opacity: "遮罩透明度",
-    animation: "切换动画",
-    autoStart: "开机自启动",
-    language: "语言",
-    footerInfo: "当鼠标移至某一显示器时，其他显示器将自动显示遮罩",
-    detecting: "检测中...",
-    detected: "已检测到",
-    monitors: "台显示器",
-    noMonitors: "未检测到显示器",
-    detectFailed: "检测失败",
-    animNone: "无",
-    animFast: "快",
-    animMedium: "中",
-    animSlow: "慢",
-  },
-  en: {
-    eyeCareMode: "Eye Care Mode",
-    opacity: "Mask Opacity",
-    animation: "Animation Speed",
-    autoStart: "Auto Start",
-    language: "Language",
-    footerInfo:
-      "When mouse moves to a monitor, other monitors will show overlay",
-    detecting: "Detecting...",
-    detected: "Detected",
-    monitors: "monitors",
-    noMonitors: "No monitors detected",
-    detectFailed: "Detection failed",
-    animNone: "None",
-    animFast: "Fast",
-    animMedium: "Medium",
-    animSlow: "Slow",
-  },
-  ja: {
-    eyeCareMode: "アイケアモード",
-    opacity: "マスク透明度",
-    animation: "アニメーション速度",
-    autoStart: "自動起動",
-    language: "言語",
-    footerInfo:
-      "マウスがモニターに移動すると、他のモニターにマスクが表示されます",
-    detecting: "検出中...",
-    detected: "検出しました",
-    monitors: "台のモニター",
-    noMonitors: "モニターが検出されません",
-    detectFailed: "検出失敗",
-    animNone: "なし",
-    animFast: "速い",
-    animMedium: "中",
-    animSlow: "遅い",
-  },
-  fr: {
-    eyeCareMode: "Mode Protection des Yeux",
-    opacity: "Opacité du Masque",
-    animation: "Vitesse d'Animation",
-    autoStart: "Démarrage Auto",
-    language: "Langue",
-    footerInfo:
-      "Lorsque la souris se déplace vers un moniteur, les autres moniteurs affichent un masque",
-    detecting: "Détection...",
-    detected: "Détecté",
-    monitors: "moniteurs",
-    noMonitors: "Aucun moniteur détecté",
-    detectFailed: "Échec de la détection",
-    animNone: "Aucune",
-    animFast: "Rapide",
-    animMedium: "Moyen",
-    animSlow: "Lent",
-  },
-  de: {
-    eyeCareMode: "Augenschutzmodus",
-    opacity: "Maskendeckkraft",
-    animation: "Animationsgeschwindigkeit",
-    autoStart: "Autostart",
-    language: "Sprache",
-    footerInfo:
-      "Wenn die Maus zu einem Monitor bewegt wird, zeigen andere Monitore eine Maske an",
-    detecting: "Erkennung...",
-    detected: "Erkannt",
-    monitors: "Monitore",
-    noMonitors: "Keine Monitore erkannt",
-    detectFailed: "Erkennung fehlgeschlagen",
-    animNone: "Keine",
-    animFast: "Schnell",
-    animMedium: "Mittel",
-    animSlow: "Langsam",
-  },
-  es: {
-    eyeCareMode: "Modo Cuidado de Ojos",
-    opacity: "Opacidad de Máscara",
-    animation: "Velocidad de Animación",
-    autoStart: "Inicio Automático",
-    language: "Idioma",
-    footerInfo:
-      "Cuando el mouse se mueve a un monitor, otros monitores mostrarán una máscara",
-    detecting: "Detectando...",
-    detected: "Detectado",
-    monitors: "monitores",
-    noMonitors: "No se detectaron monitores",
-    detectFailed: "Detección fallida",
-    animNone: "Ninguna",
-    animFast: "Rápida",
-    animMedium: "Media",
-    animSlow: "Lenta",
-  },
-};
+//
+// 文本目录与托盘、后端错误消息共用（src-tauri/locales/*.json），
+// 通过 get_locale_catalog 命令获取，缺少的翻译已由后端按回退顺序补全。
 
-// 应用翻译
-function applyTranslations(lang) {
-  const t = translations[lang] || translations["zh"];
+// 用 args 替换文本中的 {name} 占位符
+function formatMessage(text, args) {
+  if (!args) {
+    return text;
+  }
+  return text.replace(/\{(\w+)\}/g, (match, name) =>
+    name in args ? String(args[name]) : match
+  );
+}
 
+// 应用翻译
+function applyTranslations() {
   // 更新所有带 data-i18n 属性的元素
   document.querySelectorAll("[data-i18n]").forEach((el) => {
     const key = el.getAttribute("data-i18n");
-    if (t[key]) {
-      el.textContent = t[key];
+    if (window.i18n.messages[key]) {
+      el.textContent = window.i18n.messages[key];
     }
   });
-
-  return t;
 }
 
 // 导出
 window.i18n = {
-  translations,
-  applyTranslations,
-  currentLang: "zh",
-  t: function (key) {
-    return translations[this.currentLang][key] || key;
+  currentLang: "auto",
+  messages: {},
+
+  // 加载指定语言的文本目录并更新界面
+  setLanguage: async function (lang) {
+    const { invoke } = window.__TAURI__.tauri;
+    this.messages = await invoke("get_locale_catalog", { language: lang });
+    this.currentLang = lang;
+    applyTranslations();
+  },
+
+  t: function (key, args) {
+    return formatMessage(this.messages[key] || key, args);
+  },
+
+  // 本地化后端返回的配置错误（{ code, ...fields }），与 i18n::error_message 一致
+  error: function (error) {
+    if (!error || !error.code) {
+      return String(error);
+    }
+    const args = {};
+    for (const [name, value] of Object.entries(error)) {
+      args[name] = Array.isArray(value) ? value.join(", ") : value;
+    }
+    return this.t(`error.${error.code}`, args);
   },
 };
@@ -29,6 +29,10 @@ window.addEventListener("DOMContentLoaded", async () => {
   // 全局状态
   let monitors = [];
   let currentMonitorId = null;
+  let lockedMonitorId = null;
+  let pausedUntil = null;
+  let onBreak = false;
+  let focusStatus = null;
   let uiRects = [];
 
   // DOM 元素
@@ -42,6 +46,12 @@ window.addEventListener("DOMContentLoaded", async () => {
   const animationSelect = document.getElementById("animationSelect");
   const animationValue = document.getElementById("animationValue");
   const languageSelect = document.getElementById("languageSelect");
+  const breaksToggle = document.getElementById("breaksToggle");
+  const breakIntervalSelect = document.getElementById("breakIntervalSelect");
+  const idleDimSelect = document.getElementById("idleDimSelect");
+  const recentMonitorsSelect = document.getElementById("recentMonitorsSelect");
+  const focusToggle = document.getElementById("focusToggle");
+  const focusValue = document.getElementById("focusValue");
 
   // 初始化应用
   async function init() {
@@ -51,16 +61,22 @@ window.addEventListener("DOMContentLoaded", async () => {
       console.log("加载的配置:", config);
 
       // 先应用语言设置（确保翻译可用）
-      const lang = config.language || "zh";
-      window.i18n.currentLang = lang;
-      window.i18n.applyTranslations(lang);
+      const lang = config.language || "auto";
+      await window.i18n.setLanguage(lang);
       languageSelect.value = lang;
+      canvas.title = window.i18n.t("lockHint");
+
+      // 应用主题（auto 时由后端检测桌面的浅色/深色设置）
+      applyTheme(await invoke("get_theme"));
 
       // 然后设置其他配置
       opacitySlider.value = config.opacity * 100;
       opacityValue.textContent = `${Math.round(config.opacity * 100)}%`;
       enabledToggle.checked = config.enabled;
       autoStartToggle.checked = config.auto_start;
+      applyBreakConfig(config.breaks);
+      applyIdleDim(config.idle_dim_minutes);
+      applyRecentMonitors(config.recent_monitors);
 
       // 设置动画选择器的值
       const animDuration = config.animation_duration || 0;
@@ -90,6 +106,54 @@ window.addEventListener("DOMContentLoaded", async () => {
     }
   }
 
+  // 显示休息提醒设置（间隔不在选项中时添加该选项）
+  function applyBreakConfig(breaks) {
+    breaksToggle.checked = breaks.enabled;
+    const interval = breaks.interval_minutes.toString();
+    if (![...breakIntervalSelect.options].some((option) => option.value === interval)) {
+      breakIntervalSelect.add(new Option(interval, interval));
+    }
+    breakIntervalSelect.value = interval;
+  }
+
+  // 显示离开后变暗的等待时长（不在选项中时添加该选项）
+  function applyIdleDim(minutes) {
+    const value = (minutes || 0).toString();
+    if (![...idleDimSelect.options].some((option) => option.value === value)) {
+      idleDimSelect.add(new Option(value, value));
+    }
+    idleDimSelect.value = value;
+    // 选项文本随语言更新
+    for (const option of idleDimSelect.options) {
+      if (option.value !== "0") {
+        option.textContent = window.i18n.t("minuteOption", { minutes: option.value });
+      }
+    }
+  }
+
+  // 显示保持不变暗的最近使用显示器数量（不在选项中时添加该选项）
+  function applyRecentMonitors(recent) {
+    const value = ((recent && recent.count) || 1).toString();
+    if (![...recentMonitorsSelect.options].some((option) => option.value === value)) {
+      recentMonitorsSelect.add(new Option(value, value));
+    }
+    recentMonitorsSelect.value = value;
+  }
+
+  // 显示专注时段状态（开关和今天完成的次数）
+  function applyFocusStatus(status) {
+    focusStatus = status;
+    focusToggle.checked = status.active;
+    focusValue.textContent = status.completed_today
+      ? `${window.i18n.t("focusCompleted")} ${status.completed_today}`
+      : "";
+  }
+
+  // 应用主题
+  function applyTheme(info) {
+    document.documentElement.dataset.theme = info.theme;
+  }
+
   // 加载显示器信息
   async function loadMonitors() {
     try {
@@ -108,14 +172,13 @@ window.addEventListener("DOMContentLoaded", async () => {
       console.log("UI布局:", uiRects);
 
       currentMonitorId = await invoke("get_current_monitor");
-      console.log("当前显示器ID:", currentMonitorId);
-
-      if (monitors.length === 0) {
-        monitorStatus.textContent = window.i18n.t("noMonitors");
-      } else {
-        monitorStatus.textContent = `${window.i18n.t("detected")} ${monitors.length} ${window.i18n.t("monitors")}`;
-      }
+      lockedMonitorId = await invoke("get_focus_lock");
+      pausedUntil = (await invoke("get_pause_state")).paused_until;
+      onBreak = (await invoke("get_break_status")).on_break;
+      applyFocusStatus(await invoke("get_focus_session"));
+      console.log("当前显示器ID:", currentMonitorId, "锁定:", lockedMonitorId);
 
+      updateMonitorStatus();
       drawMonitors();
     } catch (error) {
       console.error("加载显示器信息失败:", error);
@@ -123,6 +186,39 @@ window.addEventListener("DOMContentLoaded", async () => {
     }
   }
 
+  // 更新显示器状态文本
+  function updateMonitorStatus() {
+    if (monitors.length === 0) {
+      monitorStatus.textContent = window.i18n.t("noMonitors");
+      return;
+    }
+
+    let text = `${window.i18n.t("detected")} ${monitors.length} ${window.i18n.t("monitors")}`;
+    const lockedIndex = uiRects.findIndex((rect) => rect.id === lockedMonitorId);
+    if (lockedIndex >= 0) {
+      text += ` · 🔒 ${window.i18n.t("locked")} ${lockedIndex + 1}`;
+    }
+    if (pausedUntil) {
+      const time = new Date(pausedUntil * 1000).toLocaleTimeString([], {
+        hour: "2-digit",
+        minute: "2-digit",
+      });
+      text += ` · ⏸ ${window.i18n.t("pausedUntil")} ${time}`;
+    }
+    if (onBreak) {
+      text += ` · ☕ ${window.i18n.t("onBreak")}`;
+    }
+    if (focusStatus && focusStatus.active) {
+      const key = focusStatus.phase === "break" ? "focusBreakUntil" : "focusUntil";
+      const time = new Date(focusStatus.ends_at * 1000).toLocaleTimeString([], {
+        hour: "2-digit",
+        minute: "2-digit",
+      });
+      text += ` · 🍅 ${window.i18n.t(key)} ${time}`;
+    }
+    monitorStatus.textContent = text;
+  }
+
   // 更新动画文本
   function updateAnimationText(duration) {
     const animationTexts = {
@@ -175,14 +271,19 @@ window.addEventListener("DOMContentLoaded", async () => {
     const offsetX = (canvasWidth - contentWidth * scale) / 2 - minX * scale;
     const offsetY = (canvasHeight - contentHeight * scale) / 2 - minY * scale;
 
+    // 记录绘制位置，用于点击锁定
+    drawnRects = [];
+
     // 绘制每个显示器
     uiRects.forEach((rect, index) => {
       const isActive = rect.id === currentMonitorId;
+      const isLocked = rect.id === lockedMonitorId;
 
       const x = rect.x * scale + offsetX;
       const y = rect.y * scale + offsetY;
       const w = rect.width * scale;
       const h = rect.height * scale;
+      drawnRects.push({ id: rect.id, x, y, w, h });
 
       // 绘制矩形（极简黑白风格）
       ctx.fillStyle = isActive ? "#000000" : "#f5f5f5";
@@ -216,9 +317,38 @@ window.addEventListener("DOMContentLoaded", async () => {
         x + w / 2,
         y + h / 2 + numberSize * 0.5
       );
+
+      // 锁定标记
+      if (isLocked) {
+        ctx.font = `${resolutionSize}px sans-serif`;
+        ctx.textAlign = "right";
+        ctx.textBaseline = "top";
+        ctx.fillText("🔒", x + w - 4, y + 4);
+      }
     });
   }
 
+  // 点击显示器锁定，再次点击已锁定的显示器解除锁定
+  let drawnRects = [];
+  canvas.addEventListener("click", async (e) => {
+    const bounds = canvas.getBoundingClientRect();
+    const px = ((e.clientX - bounds.left) * canvas.width) / bounds.width;
+    const py = ((e.clientY - bounds.top) * canvas.height) / bounds.height;
+
+    const hit = drawnRects.find(
+      (rect) => px >= rect.x && px <= rect.x + rect.w && py >= rect.y && py <= rect.y + rect.h
+    );
+    if (!hit) return;
+
+    try {
+      await invoke("set_focus_lock", {
+        monitorId: hit.id === lockedMonitorId ? null : hit.id,
+      });
+    } catch (error) {
+      console.error("锁定显示器失败:", error);
+    }
+  });
+
   // 绑定事件
   function bindEvents() {
     // 透明度滑条 - 实时更新显示
@@ -231,7 +361,7 @@ window.addEventListener("DOMContentLoaded", async () => {
     opacitySlider.addEventListener("change", async (e) => {
       const value = parseInt(e.target.value) / 100;
       try {
-        await invoke("update_opacity", { opacity: value });
+        await invoke("patch_config", { patch: { opacity: value } });
       } catch (error) {
         console.error("更新透明度失败:", error);
       }
@@ -240,7 +370,7 @@ window.addEventListener("DOMContentLoaded", async () => {
     // 护眼模式开关
     enabledToggle.addEventListener("change", async (e) => {
       try {
-        await invoke("update_enabled", { enabled: e.target.checked });
+        await invoke("patch_config", { patch: { enabled: e.target.checked } });
       } catch (error) {
         console.error("更新启用状态失败:", error);
         e.target.checked = !e.target.checked; // 回滚
@@ -250,19 +380,70 @@ window.addEventListener("DOMContentLoaded", async () => {
     // 开机自启动
     autoStartToggle.addEventListener("change", async (e) => {
       try {
-        await invoke("update_auto_start", { autoStart: e.target.checked });
+        await invoke("patch_config", { patch: { auto_start: e.target.checked } });
       } catch (error) {
         console.error("更新自启动失败:", error);
         e.target.checked = !e.target.checked; // 回滚
       }
     });
 
+    // 休息提醒
+    breaksToggle.addEventListener("change", async (e) => {
+      try {
+        await invoke("patch_config", { patch: { breaks: { enabled: e.target.checked } } });
+      } catch (error) {
+        console.error("更新休息提醒失败:", error);
+        e.target.checked = !e.target.checked; // 回滚
+      }
+    });
+
+    breakIntervalSelect.addEventListener("change", async (e) => {
+      const minutes = parseInt(e.target.value);
+      try {
+        await invoke("patch_config", { patch: { breaks: { interval_minutes: minutes } } });
+      } catch (error) {
+        console.error("更新休息间隔失败:", error);
+      }
+    });
+
+    // 离开后全部变暗
+    idleDimSelect.addEventListener("change", async (e) => {
+      const minutes = parseInt(e.target.value);
+      try {
+        await invoke("patch_config", { patch: { idle_dim_minutes: minutes } });
+      } catch (error) {
+        console.error("更新离开后变暗失败:", error);
+      }
+    });
+
+    // 最近使用的显示器不变暗
+    recentMonitorsSelect.addEventListener("change", async (e) => {
+      const count = parseInt(e.target.value);
+      try {
+        await invoke("patch_config", { patch: { recent_monitors: { count } } });
+      } catch (error) {
+        console.error("更新最近使用的显示器失败:", error);
+      }
+    });
+
+    // 专注时段
+    focusToggle.addEventListener("change", async (e) => {
+      try {
+        const command = e.target.checked ? "start_focus_session" : "stop_focus_session";
+        applyFocusStatus(await invoke(command));
+        updateMonitorStatus();
+      } catch (error) {
+        console.error("切换专注时段失败:", error);
+        e.target.checked = !e.target.checked; // 回滚
+      }
+    });
+
     // 动画速度选择
     animationSelect.addEventListener("change", async (e) => {
       const duration = parseInt(e.target.value);
       updateAnimationText(duration);
       try {
-        await invoke("update_animation_duration", { duration });
+        await invoke("patch_config", { patch: { animation_duration: duration } });
       } catch (error) {
         console.error("更新动画时长失败:", error);
       }
@@ -271,10 +452,10 @@ window.addEventListener("DOMContentLoaded", async () => {
     // 语言切换
     languageSelect.addEventListener("change", async (e) => {
       const lang = e.target.value;
-      window.i18n.currentLang = lang;
-      window.i18n.applyTranslations(lang);
       try {
-        await invoke("update_language", { language: lang });
+        await window.i18n.setLanguage(lang);
+        canvas.title = window.i18n.t("lockHint");
+        await invoke("patch_config", { patch: { language: lang } });
         await loadMonitors(); // 重新加载以更新文本
       } catch (error) {
         console.error("更新语言失败:", error);
@@ -290,15 +471,65 @@ window.addEventListener("DOMContentLoaded", async () => {
       drawMonitors();
     });
 
-    // 监听切换护眼模式（从托盘触发）
-    listen("toggle-shield", async () => {
-      enabledToggle.checked = !enabledToggle.checked;
-      try {
-        await invoke("update_enabled", { enabled: enabledToggle.checked });
-      } catch (error) {
-        console.error("切换护眼模式失败:", error);
-        enabledToggle.checked = !enabledToggle.checked;
+    // 监听显示器接入或移除
+    listen("monitors-changed", () => {
+      loadMonitors();
+    });
+
+    // 监听显示器锁定状态变化（快捷键、托盘、点击布局）
+    listen("focus-lock-changed", (event) => {
+      lockedMonitorId = event.payload;
+      updateMonitorStatus();
+      drawMonitors();
+    });
+
+    // 监听暂停状态变化（托盘、到期自动恢复）
+    listen("pause-changed", (event) => {
+      pausedUntil = event.payload.paused_until;
+      updateMonitorStatus();
+    });
+
+    // 监听休息状态变化（开始、结束、推迟、跳过）
+    listen("break-changed", (event) => {
+      onBreak = event.payload.on_break;
+      updateMonitorStatus();
+    });
+
+    // 监听专注时段变化（托盘、阶段到期）
+    listen("focus-session-changed", (event) => {
+      applyFocusStatus(event.payload);
+      updateMonitorStatus();
+    });
+
+    // 监听主题变化（切换设置或系统切换浅色/深色）
+    listen("theme-changed", (event) => {
+      applyTheme(event.payload);
+    });
+
+    // 监听配置变化（外部修改配置文件等）
+    listen("config-changed", async (event) => {
+      const config = event.payload;
+      const lang = config.language || "auto";
+      if (lang !== window.i18n.currentLang) {
+        await window.i18n.setLanguage(lang);
+        languageSelect.value = lang;
+        canvas.title = window.i18n.t("lockHint");
       }
+      opacitySlider.value = config.opacity * 100;
+      opacityValue.textContent = `${Math.round(config.opacity * 100)}%`;
+      enabledToggle.checked = config.enabled;
+      autoStartToggle.checked = config.auto_start;
+      applyBreakConfig(config.breaks);
+      applyIdleDim(config.idle_dim_minutes);
+      applyRecentMonitors(config.recent_monitors);
+      animationSelect.value = (config.animation_duration || 0).toString();
+      updateAnimationText(config.animation_duration || 0);
+    });
+
+    // 监听无效的配置文件修改
+    listen("config-error", (event) => {
+      console.error("配置文件无效，已忽略:", event.payload);
+      monitorStatus.textContent = `${window.i18n.t("configIgnored")}: ${window.i18n.error(event.payload)}`;
     });
 
     // 监听检查更新事件（从托盘触发）
@@ -308,13 +539,14 @@ window.addEventListener("DOMContentLoaded", async () => {
         const update = await checkUpdate();
         
         if (update.shouldUpdate) {
-          const msg = window.i18n.currentLang === 'zh' 
-            ? `发现新版本 ${update.manifest.version}！\n\n更新内容：\n${update.manifest.body}\n\n是否立即下载并安装？`
-            : `New version ${update.manifest.version} available!\n\n${update.manifest.body}\n\nDownload and install now?`;
+          const msg = window.i18n.t("update.available", {
+            version: update.manifest.version,
+            notes: update.manifest.body,
+          });
           
           const { ask } = window.__TAURI__.dialog;
           const yes = await ask(msg, { 
-            title: window.i18n.currentLang === 'zh' ? '发现新版本' : 'Update Available',
+            title: window.i18n.t("update.availableTitle"),
             type: 'info'
           });
           
@@ -325,11 +557,8 @@ window.addEventListener("DOMContentLoaded", async () => {
           }
         } else {
           const { message } = window.__TAURI__.dialog;
-          const msg = window.i18n.currentLang === 'zh' 
-            ? '当前已是最新版本！' 
-            : 'You are using the latest version!';
-          await message(msg, { 
-            title: window.i18n.currentLang === 'zh' ? '检查更新' : 'Check Updates',
+          await message(window.i18n.t("update.upToDate"), { 
+            title: window.i18n.t("update.upToDateTitle"),
             type: 'info'
           });
         }
@@ -342,21 +571,15 @@ window.addEventListener("DOMContentLoaded", async () => {
         const errorStr = error.toString();
         
         if (errorStr.includes('Network Error') || errorStr.includes('404')) {
-          errorMsg = window.i18n.currentLang === 'zh' 
-            ? '无法连接到更新服务器。\n\n可能原因：\n1. 网络连接问题\n2. 更新服务器暂时不可用\n3. 还没有发布新版本\n\n请稍后重试或访问 GitHub Releases 页面手动下载。' 
-            : 'Cannot connect to update server.\n\nPossible reasons:\n1. Network connection issue\n2. Update server temporarily unavailable\n3. No new version released yet\n\nPlease try again later or visit GitHub Releases page.';
+          errorMsg = window.i18n.t("update.networkError");
         } else if (errorStr.includes('control character')) {
-          errorMsg = window.i18n.currentLang === 'zh' 
-            ? '更新配置文件格式错误。\n\n这通常是因为新版本正在构建中。\n请稍后再试，或访问 GitHub 查看发布状态。' 
-            : 'Update manifest format error.\n\nThis usually means a new version is being built.\nPlease try again later or check GitHub for release status.';
+          errorMsg = window.i18n.t("update.manifestError");
         } else {
-          errorMsg = window.i18n.currentLang === 'zh' 
-            ? `检查更新失败：${error}\n\n请访问 GitHub Releases 页面查看最新版本。` 
-            : `Failed to check updates: ${error}\n\nPlease visit GitHub Releases page for the latest version.`;
+          errorMsg = window.i18n.t("update.failed", { error: errorStr });
         }
         
         await message(errorMsg, { 
-          title: window.i18n.currentLang === 'zh' ? '检查更新失败' : 'Update Check Failed',
+          title: window.i18n.t("update.failedTitle"),
           type: 'warning'
         });
       }